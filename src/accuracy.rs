@@ -0,0 +1,245 @@
+// accuracy.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+
+// The accuracy of the sums behind mean, tss and variance.
+//
+// The functions at the crate root add in plain floating point, whose
+// rounding errors grow with the number of values, and lose digits when the
+// data lie far from zero relative to their spread. The functions ending in
+// _with take an Accuracy, and Accuracy::Compensated makes them add with the
+// summation of
+//
+// A. Neumaier, "Rundungsfehleranalyse einiger Verfahren zur Summation
+// endlicher Summen", Z. Angew. Math. Mech. 54, 1974,
+//
+// an improvement of Kahan summation that carries the rounding error of
+// every addition in a second term. The sum of squares about a computed mean
+// is further corrected for the error of the mean as in the corrected
+// two-pass algorithm of T. F. Chan, G. H. Golub and R. J. LeVeque,
+// "Algorithms for Computing the Sample Variance", Am. Stat. 37, 1983.
+//
+// Accuracy::Exact adds without any rounding error, keeping the sum as a list
+// of floats of increasing size, as in
+//
+// J. R. Shewchuk, "Adaptive Precision Floating-Point Arithmetic and Fast
+// Robust Geometric Predicates", Discrete Comput. Geom. 18, 1997,
+//
+// like math.fsum of Python. The mean is then correctly rounded and the tss
+// and variance are rounded a few times at the end only, so the results don't
+// depend on the order of the data.
+
+use std::cmp::Ordering;
+
+// Accuracy selects how the sums are accumulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accuracy {
+    // plain summation, like the functions without an accuracy
+    Fast,
+    // compensated summation, about twice the work of Fast
+    Compensated,
+    // exact summation, several times the work of Fast
+    Exact,
+}
+
+// NeumaierSum adds values with a compensation for the rounding errors, so
+// that the sum is about as accurate as if it were accumulated in twice the
+// precision
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NeumaierSum {
+    sum: f64,
+    c: f64,
+}
+
+impl NeumaierSum {
+    pub fn new() -> NeumaierSum {
+        NeumaierSum { sum: 0.0, c: 0.0 }
+    }
+
+    // add adds x, keeping the low order bits lost from the larger of the sum
+    // and x
+    pub fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.c += (self.sum - t) + x;
+        } else {
+            self.c += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    // sum returns the compensated sum of the values added
+    pub fn sum(&self) -> f64 {
+        self.sum + self.c
+    }
+
+    // parts returns the compensated sum as the rounded sum and the part lost
+    // in rounding it
+    pub(crate) fn parts(&self) -> (f64, f64) {
+        two_sum(self.sum, self.c)
+    }
+}
+
+// two_sum returns a + b as the rounded sum and its rounding error
+pub(crate) fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+// two_product returns a b as the rounded product and its rounding error
+pub(crate) fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+// ExactSum adds values without rounding, like math.fsum of Python. The sum is
+// kept as nonoverlapping partial sums of increasing magnitude, and only
+// rounded when it is read.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExactSum {
+    partials: Vec<f64>,
+    // the sum of the infinities and NaNs added, which can't be kept exactly
+    special: f64,
+}
+
+impl ExactSum {
+    pub fn new() -> ExactSum {
+        ExactSum {
+            partials: Vec::new(),
+            special: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, x: f64) {
+        if !x.is_finite() {
+            self.special += x;
+            return;
+        }
+
+        let mut x = x;
+        let mut i = 0;
+        for j in 0..self.partials.len() {
+            let y = self.partials[j];
+            let (hi, lo) = if x.abs() < y.abs() { two_sum(y, x) } else { two_sum(x, y) };
+            if lo != 0.0 {
+                self.partials[i] = lo;
+                i += 1;
+            }
+            x = hi;
+        }
+        self.partials.truncate(i);
+        self.partials.push(x);
+    }
+
+    // add_product adds a b exactly
+    pub fn add_product(&mut self, a: f64, b: f64) {
+        let (p, e) = two_product(a, b);
+        self.add(p);
+        self.add(e);
+    }
+
+    // add_square adds the square of the sum s exactly
+    pub(crate) fn add_square(&mut self, s: &ExactSum) {
+        if s.special != 0.0 || s.special.is_nan() {
+            self.add(s.special * s.special);
+            return;
+        }
+        for &x in &s.partials {
+            for &y in &s.partials {
+                self.add_product(x, y);
+            }
+        }
+    }
+
+    // sum returns the sum correctly rounded, ties to even
+    pub fn sum(&self) -> f64 {
+        if self.special != 0.0 || self.special.is_nan() {
+            return self.special;
+        }
+
+        let p = &self.partials;
+        let mut n = p.len();
+        if n == 0 {
+            return 0.0;
+        }
+        n -= 1;
+        let mut hi = p[n];
+        let mut lo = 0.0;
+        while n > 0 {
+            n -= 1;
+            let (s, e) = two_sum(hi, p[n]);
+            hi = s;
+            lo = e;
+            if lo != 0.0 {
+                break;
+            }
+        }
+
+        // hi + lo is a tie that rounded hi to even, but the partials below
+        // tip it to the other side
+        if n > 0 && ((lo < 0.0 && p[n - 1] < 0.0) || (lo > 0.0 && p[n - 1] > 0.0)) {
+            let y = lo * 2.0;
+            let x = hi + y;
+            if x - hi == y {
+                hi = x;
+            }
+        }
+        hi
+    }
+
+    // div returns the sum divided by the positive sum d, correctly rounded
+    pub fn div(&self, d: &ExactSum) -> f64 {
+        let mut q = self.sum() / d.sum();
+        if !q.is_finite() || q == 0.0 {
+            return q;
+        }
+
+        // move q to the float nearest to the quotient, comparing the sum
+        // exactly with d times the midpoints between q and its neighbours
+        loop {
+            let up = q.next_up();
+            match self.cmp_product(d, q, (up - q) / 2.0) {
+                Ordering::Greater => {
+                    q = up;
+                    continue;
+                }
+                Ordering::Equal => return if q.to_bits() & 1 == 0 { q } else { up },
+                Ordering::Less => {}
+            }
+
+            let down = q.next_down();
+            match self.cmp_product(d, q, (down - q) / 2.0) {
+                Ordering::Less => q = down,
+                Ordering::Equal => return if q.to_bits() & 1 == 0 { q } else { down },
+                Ordering::Greater => return q,
+            }
+        }
+    }
+
+    // cmp_product compares the sum with d (q + h)
+    fn cmp_product(&self, d: &ExactSum, q: f64, h: f64) -> Ordering {
+        let mut r = self.clone();
+        for &x in &d.partials {
+            r.add_product(-x, q);
+            r.add_product(-x, h);
+        }
+        r.sum().partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+    }
+}
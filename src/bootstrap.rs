@@ -0,0 +1,150 @@
+// bootstrap.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Bootstrap confidence intervals for any statistic of a dataset.
+//
+// The statistic is a function of a slice of doubles, so that the functions
+// of this crate can be used directly:
+//
+//     let mut rng = Xoshiro256StarStar::new(42);
+//     let b = bootstrap(data, mean, 2000, &mut rng);
+//     let (lower, upper) = b.bca_interval(0.95);
+//
+// See B. Efron and R. J. Tibshirani, "An Introduction to the Bootstrap",
+// Chapman & Hall, 1993.
+
+use data::Data;
+use jackknife::leave_one_out;
+use rng::Rng;
+use specfunc::{normal_cdf, normal_quantile};
+use types::F64;
+use {mean, quantile_from_sorted_data, sd, sorted_f64};
+
+// Bootstrap holds the bootstrap distribution of a statistic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bootstrap {
+    // the statistic of the original data
+    pub estimate: f64,
+    // the statistic of every resample, in increasing order
+    pub replicates: Vec<f64>,
+    // the standard deviation of the replicates
+    pub std_error: f64,
+    // the mean of the replicates minus the estimate
+    pub bias: f64,
+    // the acceleration of the BCa interval, found with the jackknife
+    acceleration: f64,
+}
+
+// bootstrap resamples the data with replacement n_resamples times and
+// calculates the statistic of every resample
+pub fn bootstrap<T, D, F, R>(data: &D, statistic: F, n_resamples: usize, rng: &mut R) -> Bootstrap
+    where T: F64,
+          D: Data<T> + ?Sized,
+          F: Fn(&[f64]) -> f64,
+          R: Rng
+{
+    assert!(data.len() > 1, "bootstrapping needs at least two values");
+    assert!(n_resamples > 1, "bootstrapping needs at least two resamples");
+
+    let data: Vec<f64> = data.iter().map(|x| x.f64()).collect();
+    let n = data.len();
+    let estimate = statistic(&data);
+
+    let mut resample = vec![0.0; n];
+    let mut replicates = Vec::with_capacity(n_resamples);
+    for _ in 0..n_resamples {
+        for x in &mut resample {
+            *x = data[rng.uniform_int(n as u64) as usize];
+        }
+        replicates.push(statistic(&resample));
+    }
+
+    let std_error = sd(&replicates);
+    let bias = mean(&replicates) - estimate;
+    let replicates = sorted_f64(&replicates);
+
+    Bootstrap {
+        estimate,
+        replicates,
+        std_error,
+        bias,
+        acceleration: acceleration(&data, &statistic),
+    }
+}
+
+// acceleration estimates the acceleration constant of the BCa interval from
+// the leave-one-out values of the statistic:
+//
+// a = Sum (t_mean - t_i)^3 / (6 (Sum (t_mean - t_i)^2)^(3/2))
+//
+fn acceleration<F: Fn(&[f64]) -> f64>(data: &[f64], statistic: &F) -> f64 {
+    let loo = leave_one_out(data, statistic);
+    let loo_mean = mean(&loo);
+    let mut num = 0.0;
+    let mut den = 0.0;
+    for t in &loo {
+        let d = loo_mean - t;
+        num += d * d * d;
+        den += d * d;
+    }
+
+    if den > 0.0 {
+        num / (6.0 * den.powf(1.5))
+    } else {
+        0.0
+    }
+}
+
+impl Bootstrap {
+    // percentile_interval returns the interval between the (1 - level)/2 and
+    // (1 + level)/2 quantiles of the replicates
+    pub fn percentile_interval(&self, level: f64) -> (f64, f64) {
+        let alpha = (1.0 - level) / 2.0;
+        (quantile_from_sorted_data(&self.replicates, alpha),
+         quantile_from_sorted_data(&self.replicates, 1.0 - alpha))
+    }
+
+    // basic_interval returns the basic (reverse percentile) interval
+    // (2 estimate - q_upper, 2 estimate - q_lower)
+    pub fn basic_interval(&self, level: f64) -> (f64, f64) {
+        let (lower, upper) = self.percentile_interval(level);
+        (2.0 * self.estimate - upper, 2.0 * self.estimate - lower)
+    }
+
+    // bca_interval returns the bias-corrected and accelerated interval, the
+    // percentile interval with quantiles corrected for the median bias and
+    // the skewness of the bootstrap distribution
+    pub fn bca_interval(&self, level: f64) -> (f64, f64) {
+        let b = self.replicates.len() as f64;
+        let below = self.replicates.iter().filter(|&&t| t < self.estimate).count() as f64;
+
+        // keep the bias correction finite when all replicates lie on one side
+        let p0 = (below / b).max(0.5 / b).min(1.0 - 0.5 / b);
+        let z0 = normal_quantile(p0);
+
+        let alpha = (1.0 - level) / 2.0;
+        let a = self.acceleration;
+        let corrected = |z: f64| normal_cdf(z0 + (z0 + z) / (1.0 - a * (z0 + z)));
+
+        let lower = corrected(normal_quantile(alpha));
+        let upper = corrected(normal_quantile(1.0 - alpha));
+        (quantile_from_sorted_data(&self.replicates, lower),
+         quantile_from_sorted_data(&self.replicates, upper))
+    }
+}
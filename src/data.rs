@@ -0,0 +1,190 @@
+// data.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// The sequences of values accepted by the statistics functions.
+//
+// Besides slices, arrays and vectors, the functions accept a StridedSlice,
+// which plays the role of the stride argument of the GSL functions. It views
+// every stride-th value of a buffer, so the statistics of one channel of
+// interleaved data can be computed without copying:
+//
+//     // left and right samples of a stereo buffer
+//     let left = StridedSlice::new(&buffer, 2);
+//     let right = StridedSlice::new(&buffer[1..], 2);
+//     let (l, r) = (mean(&left), mean(&right));
+//
+
+use std::iter::StepBy;
+use std::ops::Index;
+use std::slice;
+
+// Data is a sequence of values of type T with random access
+pub trait Data<T> {
+    type Iter<'a>: Iterator<Item = &'a T>
+        where Self: 'a,
+              T: 'a;
+
+    // len returns the number of values
+    fn len(&self) -> usize;
+
+    // iter returns an iterator over the values in order
+    fn iter(&self) -> Self::Iter<'_>;
+
+    // at returns the value at position i
+    fn at(&self, i: usize) -> &T;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Data<T> for [T] {
+    type Iter<'a> = slice::Iter<'a, T> where T: 'a;
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        <[T]>::iter(self)
+    }
+
+    fn at(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+impl<T, const N: usize> Data<T> for [T; N] {
+    type Iter<'a> = slice::Iter<'a, T> where T: 'a;
+
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self[..].iter()
+    }
+
+    fn at(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+impl<T> Data<T> for Vec<T> {
+    type Iter<'a> = slice::Iter<'a, T> where T: 'a;
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self[..].iter()
+    }
+
+    fn at(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
+
+impl<T, D: Data<T> + ?Sized> Data<T> for &D {
+    type Iter<'a> = D::Iter<'a> where Self: 'a, T: 'a;
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        (**self).iter()
+    }
+
+    fn at(&self, i: usize) -> &T {
+        (**self).at(i)
+    }
+}
+
+// strided
+
+// StridedSlice views the values 0, stride, 2 stride, ... of a slice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StridedSlice<'a, T: 'a> {
+    data: &'a [T],
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, T> StridedSlice<'a, T> {
+    // new views every stride-th value of data, starting with the first
+    pub fn new(data: &'a [T], stride: usize) -> StridedSlice<'a, T> {
+        assert!(stride > 0, "stride must be positive");
+        StridedSlice {
+            data,
+            stride,
+            len: data.len().div_ceil(stride),
+        }
+    }
+
+    // with_len views n values with the given stride, like the arguments
+    // (data, stride, n) of the GSL functions
+    pub fn with_len(data: &'a [T], stride: usize, n: usize) -> StridedSlice<'a, T> {
+        assert!(stride > 0, "stride must be positive");
+        assert!(n == 0 || (n - 1) * stride < data.len(),
+                "n values with this stride exceed the data");
+        StridedSlice {
+            data,
+            stride,
+            len: n,
+        }
+    }
+
+    // channel views channel c of data interleaving n_channels channels
+    pub fn channel(data: &'a [T], n_channels: usize, c: usize) -> StridedSlice<'a, T> {
+        assert!(c < n_channels, "channel c must be less than n_channels");
+        StridedSlice::new(&data[c.min(data.len())..], n_channels)
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+impl<'a, T> Index<usize> for StridedSlice<'a, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        assert!(i < self.len, "index out of range");
+        &self.data[i * self.stride]
+    }
+}
+
+impl<'s, T> Data<T> for StridedSlice<'s, T> {
+    type Iter<'a> = StepBy<slice::Iter<'a, T>> where Self: 'a, T: 'a;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        let end = if self.len == 0 { 0 } else { (self.len - 1) * self.stride + 1 };
+        self.data[..end].iter().step_by(self.stride)
+    }
+
+    fn at(&self, i: usize) -> &T {
+        &self[i]
+    }
+}
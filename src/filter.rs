@@ -0,0 +1,196 @@
+// filter.rs
+//
+// Copyright (C) 2018 Patrick Alken
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Moving window filters for removing spikes from a series, after
+// gsl_filter_median and gsl_filter_impulse.
+//
+// The window of value i holds the values i - half_window to i + half_window.
+// Near the ends of the series the window is completed as set by EndType.
+
+use std::cmp::Ordering;
+
+use data::Data;
+use types::F64;
+
+// EndType sets how the windows are completed near the ends of the series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndType {
+    // pad the series with zeros
+    PadZero,
+    // pad the series with its first and last values
+    PadValue,
+    // shrink the window to the values inside the series
+    Truncate,
+}
+
+// SortedWindow keeps the values of a sliding window in sorted order, using
+// the total order of f64 so that NaN doesn't break the search
+struct SortedWindow {
+    values: Vec<f64>,
+}
+
+impl SortedWindow {
+    fn position(&self, x: f64) -> usize {
+        self.values.partition_point(|v| v.total_cmp(&x) == Ordering::Less)
+    }
+
+    fn insert(&mut self, x: f64) {
+        let i = self.position(x);
+        self.values.insert(i, x);
+    }
+
+    fn remove(&mut self, x: f64) {
+        let i = self.position(x);
+        self.values.remove(i);
+    }
+}
+
+fn median_of_sorted(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+// moving_window calls f with the index and the sorted window of every value
+fn moving_window<F>(x: &[f64], half_window: usize, end: EndType, mut f: F)
+    where F: FnMut(usize, &[f64])
+{
+    let n = x.len();
+    let h = half_window;
+
+    // the series extended with the padding, if any
+    let ext = match end {
+        EndType::Truncate => x.to_vec(),
+        EndType::PadZero | EndType::PadValue => {
+            let (first, last) = if end == EndType::PadZero {
+                (0.0, 0.0)
+            } else {
+                (x[0], x[n - 1])
+            };
+            let mut ext = vec![first; h];
+            ext.extend_from_slice(x);
+            ext.extend(vec![last; h]);
+            ext
+        }
+    };
+
+    let mut window = SortedWindow { values: Vec::with_capacity(2 * h + 1) };
+    let (mut lo, mut hi) = (0, 0);
+    for i in 0..n {
+        // the first and one past the last position of window i in ext
+        let (new_lo, new_hi) = match end {
+            EndType::Truncate => (i.saturating_sub(h), (i + h + 1).min(n)),
+            _ => (i, i + 2 * h + 1),
+        };
+        while hi < new_hi {
+            window.insert(ext[hi]);
+            hi += 1;
+        }
+        while lo < new_lo {
+            window.remove(ext[lo]);
+            lo += 1;
+        }
+        f(i, &window.values);
+    }
+}
+
+// median
+
+// median_filter replaces every value by the median of its window of
+// 2 half_window + 1 values, like gsl_filter_median
+pub fn median_filter<T, D>(data: &D, half_window: usize, end: EndType) -> Vec<f64>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    let x: Vec<f64> = data.iter().map(|v| v.f64()).collect();
+    if x.is_empty() {
+        return x;
+    }
+
+    let mut y = vec![0.0; x.len()];
+    moving_window(&x, half_window, end, |i, w| y[i] = median_of_sorted(w));
+    y
+}
+
+// hampel
+
+// Hampel holds the outcome of hampel_filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hampel {
+    // the data with the outliers replaced by their window median
+    pub filtered: Vec<f64>,
+    // true for the values found to be outliers
+    pub outliers: Vec<bool>,
+    // the median of every window
+    pub median: Vec<f64>,
+    // the scaled median absolute deviation of every window
+    pub scale: Vec<f64>,
+}
+
+impl Hampel {
+    // n_outliers returns the number of outliers found
+    pub fn n_outliers(&self) -> usize {
+        self.outliers.iter().filter(|&&o| o).count()
+    }
+}
+
+// hampel_filter finds the values that lie more than n_sigma times the scaled
+// median absolute deviation of their window from the window median, and
+// replaces them by the median, like gsl_filter_impulse with the MAD scale.
+// The windows are truncated at the ends of the series. See R. K. Pearson et
+// al, "Generalized Hampel Filters", EURASIP J. Adv. Signal Process., 2016.
+pub fn hampel_filter<T, D>(data: &D, half_window: usize, n_sigma: f64) -> Hampel
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    assert!(n_sigma >= 0.0, "n_sigma must not be negative");
+
+    let x: Vec<f64> = data.iter().map(|v| v.f64()).collect();
+    let n = x.len();
+    let mut median = vec![0.0; n];
+    let mut scale = vec![0.0; n];
+
+    if n > 0 {
+        let mut deviations = Vec::with_capacity(2 * half_window + 1);
+        moving_window(&x, half_window, EndType::Truncate, |i, w| {
+            let m = median_of_sorted(w);
+            deviations.clear();
+            deviations.extend(w.iter().map(|v| (v - m).abs()));
+            deviations.sort_by(|a, b| a.total_cmp(b));
+            median[i] = m;
+            scale[i] = 1.482602218505602 * median_of_sorted(&deviations);
+        });
+    }
+
+    let outliers: Vec<bool> = (0..n)
+        .map(|i| (x[i] - median[i]).abs() > n_sigma * scale[i])
+        .collect();
+    let filtered = (0..n).map(|i| if outliers[i] { median[i] } else { x[i] }).collect();
+
+    Hampel {
+        filtered,
+        outliers,
+        median,
+        scale,
+    }
+}
@@ -0,0 +1,335 @@
+// float.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 Jim Davies, Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Statistics in the precision of the data, like the gsl_stats_float_*
+// functions for f32.
+//
+// The functions at the crate root convert every value to f64. The functions
+// here compute and return in the float type F of the data, with the same
+// recurrence relations. The _acc variants accumulate in the float type A
+// instead and round the result to F, so f32 data can be summed in f64:
+//
+//     let m: f32 = float::mean(&data);
+//     let m: f32 = float::mean_acc::<f64, _, _>(&data);
+//
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use data::Data;
+
+// Float is a floating point type that the statistics can be computed in
+pub trait Float: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> +
+                 Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+{
+    fn zero() -> Self;
+
+    // from_f64 rounds x to the nearest value of this type
+    fn from_f64(x: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    fn from_usize(n: usize) -> Self;
+
+    fn sqrt(self) -> Self;
+
+    fn abs(self) -> Self;
+
+    fn is_nan(self) -> bool;
+
+    // convert rounds x of another float type to this type
+    fn convert<G: Float>(x: G) -> Self {
+        Self::from_f64(x.to_f64())
+    }
+}
+
+impl Float for f32 {
+    fn zero() -> f32 {
+        0.0
+    }
+
+    fn from_f64(x: f64) -> f32 {
+        x as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_usize(n: usize) -> f32 {
+        n as f32
+    }
+
+    fn sqrt(self) -> f32 {
+        f32::sqrt(self)
+    }
+
+    fn abs(self) -> f32 {
+        f32::abs(self)
+    }
+
+    fn is_nan(self) -> bool {
+        f32::is_nan(self)
+    }
+}
+
+impl Float for f64 {
+    fn zero() -> f64 {
+        0.0
+    }
+
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_usize(n: usize) -> f64 {
+        n as f64
+    }
+
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+
+    fn abs(self) -> f64 {
+        f64::abs(self)
+    }
+
+    fn is_nan(self) -> bool {
+        f64::is_nan(self)
+    }
+}
+
+// mean
+
+fn mean_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> A {
+    let mut mean = A::zero();
+    for (i, &val) in data.iter().enumerate() {
+        mean = mean + (A::convert(val) - mean) / A::from_usize(i + 1);
+    }
+    mean
+}
+
+// mean calculates the arithmetic mean with the recurrence relation
+pub fn mean<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    mean_in::<F, F, D>(data)
+}
+
+// mean_acc is mean accumulated in A
+pub fn mean_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    F::convert(mean_in::<A, F, D>(data))
+}
+
+// absdev
+
+pub fn absdev<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    absdev_mean(data, mean(data))
+}
+
+fn absdev_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D, mean: A) -> A {
+    let mut sum = A::zero();
+    for &val in data.iter() {
+        sum = sum + (A::convert(val) - mean).abs();
+    }
+    sum / A::from_usize(data.len())
+}
+
+// absdev_mean finds the absolute deviation from the mean
+pub fn absdev_mean<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F) -> F {
+    absdev_in(data, mean)
+}
+
+// absdev_acc is absdev accumulated in A
+pub fn absdev_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean_in::<A, F, D>(data);
+    F::convert(absdev_in(data, mean))
+}
+
+// variance
+
+fn variance_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D, mean: A) -> A {
+    let mut variance = A::zero();
+    for (i, &val) in data.iter().enumerate() {
+        let delta = A::convert(val) - mean;
+        variance = variance + (delta * delta - variance) / A::from_usize(i + 1);
+    }
+    variance
+}
+
+fn sample_variance_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D, mean: A) -> A {
+    let n = data.len();
+    variance_in(data, mean) * A::from_usize(n) / A::from_usize(n - 1)
+}
+
+pub fn variance_with_fixed_mean<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F) -> F {
+    variance_in(data, mean)
+}
+
+pub fn sd_with_fixed_mean<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F) -> F {
+    variance_in(data, mean).sqrt()
+}
+
+pub fn variance_mean<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F) -> F {
+    sample_variance_in(data, mean)
+}
+
+pub fn sd_mean<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F) -> F {
+    sample_variance_in(data, mean).sqrt()
+}
+
+pub fn variance<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    variance_mean(data, mean(data))
+}
+
+pub fn sd<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    sd_mean(data, mean(data))
+}
+
+// variance_acc is variance accumulated in A
+pub fn variance_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean_in::<A, F, D>(data);
+    F::convert(sample_variance_in(data, mean))
+}
+
+// sd_acc is sd accumulated in A
+pub fn sd_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean_in::<A, F, D>(data);
+    F::convert(sample_variance_in(data, mean).sqrt())
+}
+
+// tss
+
+fn tss_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D, mean: A) -> A {
+    let mut res = A::zero();
+    for &val in data.iter() {
+        let delta = A::convert(val) - mean;
+        res = res + delta * delta;
+    }
+    res
+}
+
+// tss_mean finds the sum of squares about the mean
+pub fn tss_mean<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F) -> F {
+    tss_in(data, mean)
+}
+
+pub fn tss<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    tss_mean(data, mean(data))
+}
+
+// tss_acc is tss accumulated in A
+pub fn tss_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean_in::<A, F, D>(data);
+    F::convert(tss_in(data, mean))
+}
+
+// skew
+
+pub fn skew<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean(data);
+    skew_mean_sd(data, mean, sd_mean(data, mean))
+}
+
+fn skew_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D, mean: A, sd: A) -> A {
+    let mut skew = A::zero();
+    for (i, &val) in data.iter().enumerate() {
+        let x = (A::convert(val) - mean) / sd;
+        skew = skew + (x * x * x - skew) / A::from_usize(i + 1);
+    }
+    skew
+}
+
+// skew_mean_sd calculates the skewness of a dataset
+pub fn skew_mean_sd<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F, sd: F) -> F {
+    skew_in(data, mean, sd)
+}
+
+// skew_acc is skew accumulated in A
+pub fn skew_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean_in::<A, F, D>(data);
+    let sd = sample_variance_in(data, mean).sqrt();
+    F::convert(skew_in(data, mean, sd))
+}
+
+// kurtosis
+
+pub fn kurtosis<F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean(data);
+    kurtosis_mean_sd(data, mean, sd_mean(data, mean))
+}
+
+fn kurtosis_in<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D, mean: A, sd: A) -> A {
+    let mut avg = A::zero();
+    for (i, &val) in data.iter().enumerate() {
+        let x = (A::convert(val) - mean) / sd;
+        avg = avg + (x * x * x * x - avg) / A::from_usize(i + 1);
+    }
+    avg - A::from_usize(3) // makes kurtosis zero for a Gaussian
+}
+
+// kurtosis_mean_sd calculates the kurtosis of a dataset
+pub fn kurtosis_mean_sd<F: Float, D: Data<F> + ?Sized>(data: &D, mean: F, sd: F) -> F {
+    kurtosis_in(data, mean, sd)
+}
+
+// kurtosis_acc is kurtosis accumulated in A
+pub fn kurtosis_acc<A: Float, F: Float, D: Data<F> + ?Sized>(data: &D) -> F {
+    let mean = mean_in::<A, F, D>(data);
+    let sd = sample_variance_in(data, mean).sqrt();
+    F::convert(kurtosis_in(data, mean, sd))
+}
+
+// minmax
+
+// max finds the first largest member and its position, a NaN being returned
+// as soon as it is found
+pub fn max<F: Float, D: Data<F> + ?Sized>(data: &D) -> (F, usize) {
+    let mut max = *data.at(0);
+    let mut max_index = 0;
+    for (i, &xi) in data.iter().enumerate() {
+        if xi > max {
+            max = xi;
+            max_index = i;
+        }
+        if xi.is_nan() {
+            return (xi, i);
+        }
+    }
+    (max, max_index)
+}
+
+// min finds the first smallest member and its position, a NaN being
+// returned as soon as it is found
+pub fn min<F: Float, D: Data<F> + ?Sized>(data: &D) -> (F, usize) {
+    let mut min = *data.at(0);
+    let mut min_index = 0;
+    for (i, &xi) in data.iter().enumerate() {
+        if xi < min {
+            min = xi;
+            min_index = i;
+        }
+        if xi.is_nan() {
+            return (xi, i);
+        }
+    }
+    (min, min_index)
+}
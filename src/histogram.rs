@@ -0,0 +1,557 @@
+// histogram.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// One-dimensional histograms, a port of gsl_histogram.
+//
+// A histogram with n bins is described by n + 1 increasing range values.
+// Bin i counts the values x with range[i] <= x < range[i + 1], so the
+// upper edge of the last bin is not part of the histogram.
+
+use std::error;
+use std::fmt;
+
+use data::Data;
+use specfunc::ln_gamma;
+use types::F64;
+use {minmax, quantile_from_sorted_data, sd, skew, sorted_f64, w_mean, wsd_with_fixed_mean};
+
+// HistogramError reports why an operation on a histogram could not be done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistogramError {
+    // the value lies outside the range of the histogram
+    OutOfRange,
+    // the histograms don't have identical bin ranges
+    Mismatch,
+    // a probability distribution can't be made from negative bins
+    NegativeBin,
+    // a probability distribution can't be made from empty bins
+    Empty,
+}
+
+impl fmt::Display for HistogramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HistogramError::OutOfRange => write!(f, "value lies outside the histogram range"),
+            HistogramError::Mismatch => write!(f, "histograms have different binning"),
+            HistogramError::NegativeBin => {
+                write!(f, "histogram bins must be non-negative to compute a probability distribution")
+            }
+            HistogramError::Empty => write!(f, "histogram bins must not all be zero"),
+        }
+    }
+}
+
+impl error::Error for HistogramError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    range: Vec<f64>,
+    bin: Vec<f64>,
+}
+
+// make_uniform fills range with n + 1 equally spaced values from xmin to xmax
+pub(crate) fn make_uniform(n: usize, xmin: f64, xmax: f64) -> Vec<f64> {
+    (0..n + 1)
+        .map(|i| {
+            let f1 = (n - i) as f64 / n as f64;
+            let f2 = i as f64 / n as f64;
+            f1 * xmin + f2 * xmax
+        })
+        .collect()
+}
+
+// find_bin locates the bin containing x in the n + 1 increasing range values.
+// The uniform case is tried first, falling back to a binary search.
+pub(crate) fn find_bin(range: &[f64], x: f64) -> Option<usize> {
+    let n = range.len() - 1;
+
+    if !(x >= range[0] && x < range[n]) {
+        return None;
+    }
+
+    let u = (x - range[0]) / (range[n] - range[0]);
+    let i_linear = (u * n as f64) as usize;
+
+    if i_linear < n && x >= range[i_linear] && x < range[i_linear + 1] {
+        return Some(i_linear);
+    }
+
+    let mut lower = 0;
+    let mut upper = n;
+    while upper - lower > 1 {
+        let mid = (upper + lower) / 2;
+        if x >= range[mid] {
+            lower = mid;
+        } else {
+            upper = mid;
+        }
+    }
+    Some(lower)
+}
+
+// cumulative_sum returns the normalized cumulative sum of the bins, starting
+// at zero and ending at one, for sampling from the bins as a distribution
+pub(crate) fn cumulative_sum(bin: &[f64]) -> Result<Vec<f64>, HistogramError> {
+    if bin.iter().any(|&b| b < 0.0) {
+        return Err(HistogramError::NegativeBin);
+    }
+
+    let mut sum = Vec::with_capacity(bin.len() + 1);
+    let mut total = 0.0;
+    sum.push(total);
+    for &b in bin {
+        total += b;
+        sum.push(total);
+    }
+
+    if total <= 0.0 || total.is_nan() {
+        return Err(HistogramError::Empty);
+    }
+    for s in &mut sum {
+        *s /= total;
+    }
+    Ok(sum)
+}
+
+impl Histogram {
+    // new_uniform creates a histogram of n bins of equal width covering [xmin, xmax)
+    pub fn new_uniform(n: usize, xmin: f64, xmax: f64) -> Histogram {
+        assert!(n > 0, "histogram length n must be positive integer");
+        assert!(xmin < xmax, "xmin must be less than xmax");
+
+        Histogram {
+            range: make_uniform(n, xmin, xmax),
+            bin: vec![0.0; n],
+        }
+    }
+
+    // with_ranges creates a histogram from user-defined bin edges. A slice of
+    // n + 1 strictly increasing values gives n bins.
+    pub fn with_ranges<T: F64>(range: &[T]) -> Histogram {
+        assert!(range.len() > 1, "at least two range values are needed");
+
+        let range: Vec<f64> = range.iter().map(|r| r.f64()).collect();
+        for i in 1..range.len() {
+            assert!(range[i - 1] < range[i],
+                    "histogram bin extremes must be in increasing order");
+        }
+
+        let n = range.len() - 1;
+        Histogram {
+            range,
+            bin: vec![0.0; n],
+        }
+    }
+
+    pub(crate) fn from_parts(range: Vec<f64>, bin: Vec<f64>) -> Histogram {
+        debug_assert_eq!(range.len(), bin.len() + 1);
+        Histogram { range, bin }
+    }
+
+    // bins returns the number of bins
+    pub fn bins(&self) -> usize {
+        self.bin.len()
+    }
+
+    // range returns the n + 1 bin edges
+    pub fn range(&self) -> &[f64] {
+        &self.range
+    }
+
+    // values returns the contents of the bins
+    pub fn values(&self) -> &[f64] {
+        &self.bin
+    }
+
+    // get returns the contents of bin i
+    pub fn get(&self, i: usize) -> f64 {
+        self.bin[i]
+    }
+
+    // bin_range returns the lower and upper edge of bin i
+    pub fn bin_range(&self, i: usize) -> (f64, f64) {
+        (self.range[i], self.range[i + 1])
+    }
+
+    // max returns the upper edge of the histogram
+    pub fn max(&self) -> f64 {
+        self.range[self.bin.len()]
+    }
+
+    // min returns the lower edge of the histogram
+    pub fn min(&self) -> f64 {
+        self.range[0]
+    }
+
+    // reset sets all bins to zero
+    pub fn reset(&mut self) {
+        for b in &mut self.bin {
+            *b = 0.0;
+        }
+    }
+
+    // find returns the index of the bin that contains x
+    pub fn find(&self, x: f64) -> Option<usize> {
+        find_bin(&self.range, x)
+    }
+
+    // increment adds one to the bin that contains x
+    pub fn increment(&mut self, x: f64) -> Result<(), HistogramError> {
+        self.accumulate(x, 1.0)
+    }
+
+    // accumulate adds weight to the bin that contains x
+    pub fn accumulate(&mut self, x: f64, weight: f64) -> Result<(), HistogramError> {
+        match self.find(x) {
+            Some(i) => {
+                self.bin[i] += weight;
+                Ok(())
+            }
+            None => Err(HistogramError::OutOfRange),
+        }
+    }
+
+    // max_val returns the largest value contained in the bins
+    pub fn max_val(&self) -> f64 {
+        self.bin[self.max_bin()]
+    }
+
+    // max_bin returns the index of the first bin with the largest value
+    pub fn max_bin(&self) -> usize {
+        let mut imax = 0;
+        for (i, &b) in self.bin.iter().enumerate() {
+            if b > self.bin[imax] {
+                imax = i;
+            }
+        }
+        imax
+    }
+
+    // min_val returns the smallest value contained in the bins
+    pub fn min_val(&self) -> f64 {
+        self.bin[self.min_bin()]
+    }
+
+    // min_bin returns the index of the first bin with the smallest value
+    pub fn min_bin(&self) -> usize {
+        let mut imin = 0;
+        for (i, &b) in self.bin.iter().enumerate() {
+            if b < self.bin[imin] {
+                imin = i;
+            }
+        }
+        imin
+    }
+
+    // centers returns the midpoints of the bins
+    pub fn centers(&self) -> Vec<f64> {
+        self.range.windows(2).map(|r| (r[0] + r[1]) / 2.0).collect()
+    }
+
+    // mean returns the mean of the histogrammed variable, where each bin is
+    // treated as a point at its center weighted by its value. Negative bins
+    // are ignored.
+    pub fn mean(&self) -> f64 {
+        w_mean(&self.bin, &self.centers())
+    }
+
+    // sigma returns the standard deviation of the histogrammed variable, in
+    // the same way as mean. Negative bins are ignored.
+    pub fn sigma(&self) -> f64 {
+        let centers = self.centers();
+        let wmean = w_mean(&self.bin, &centers);
+        wsd_with_fixed_mean(&self.bin, &centers, wmean)
+    }
+
+    // sum returns the sum of all bin values, including negative ones
+    pub fn sum(&self) -> f64 {
+        self.bin.iter().sum()
+    }
+
+    // equal_bins returns true if both histograms have identical bin ranges
+    pub fn equal_bins(&self, other: &Histogram) -> bool {
+        self.range == other.range
+    }
+
+    fn check_bins(&self, other: &Histogram) -> Result<(), HistogramError> {
+        if self.equal_bins(other) {
+            Ok(())
+        } else {
+            Err(HistogramError::Mismatch)
+        }
+    }
+
+    // add adds the contents of the bins of other to this histogram
+    pub fn add(&mut self, other: &Histogram) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b += *o;
+        }
+        Ok(())
+    }
+
+    // sub subtracts the contents of the bins of other from this histogram
+    pub fn sub(&mut self, other: &Histogram) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b -= *o;
+        }
+        Ok(())
+    }
+
+    // mul multiplies the contents of the bins by the contents of other
+    pub fn mul(&mut self, other: &Histogram) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b *= *o;
+        }
+        Ok(())
+    }
+
+    // div divides the contents of the bins by the contents of other
+    pub fn div(&mut self, other: &Histogram) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b /= *o;
+        }
+        Ok(())
+    }
+
+    // scale multiplies the contents of the bins by the constant scale
+    pub fn scale(&mut self, scale: f64) {
+        for b in &mut self.bin {
+            *b *= scale;
+        }
+    }
+
+    // shift adds the constant offset to the contents of the bins
+    pub fn shift(&mut self, offset: f64) {
+        for b in &mut self.bin {
+            *b += offset;
+        }
+    }
+}
+
+// pdf
+
+// HistogramPdf is the probability distribution described by a histogram, a
+// port of gsl_histogram_pdf. Within a bin the distribution is uniform.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramPdf {
+    range: Vec<f64>,
+    sum: Vec<f64>,
+}
+
+impl HistogramPdf {
+    // new creates the distribution from a histogram with non-negative bins
+    pub fn new(h: &Histogram) -> Result<HistogramPdf, HistogramError> {
+        Ok(HistogramPdf {
+            range: h.range.clone(),
+            sum: cumulative_sum(&h.bin)?,
+        })
+    }
+
+    // range returns the n + 1 bin edges
+    pub fn range(&self) -> &[f64] {
+        &self.range
+    }
+
+    // sum returns the n + 1 values of the cumulative distribution at the bin
+    // edges, running from zero to one
+    pub fn sum(&self) -> &[f64] {
+        &self.sum
+    }
+
+    // sample returns the value drawn from the distribution for the uniform
+    // random number r in [0, 1], by inverting the cumulative distribution
+    pub fn sample(&self, r: f64) -> f64 {
+        // wrap the exclusive top of the bin down to the inclusive bottom
+        let r = if r == 1.0 { 0.0 } else { r };
+
+        let k = find_bin(&self.sum, r).expect("r must lie in [0, 1]");
+        let delta = (r - self.sum[k]) / (self.sum[k + 1] - self.sum[k]);
+        self.range[k] + delta * (self.range[k + 1] - self.range[k])
+    }
+
+    // cdf returns the probability of a value less than or equal to x
+    pub fn cdf(&self, x: f64) -> f64 {
+        match find_bin(&self.range, x) {
+            Some(k) => {
+                let delta = (x - self.range[k]) / (self.range[k + 1] - self.range[k]);
+                self.sum[k] + delta * (self.sum[k + 1] - self.sum[k])
+            }
+            None if x.is_nan() => x,
+            None if x < self.range[0] => 0.0,
+            None => 1.0,
+        }
+    }
+}
+
+// bin rules
+
+// BinRule selects how the number of bins is derived from the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinRule {
+    // ceil(log2(n)) + 1 bins, suited for roughly normal data
+    Sturges,
+    // bin width 3.49 * sd * n^(-1/3)
+    Scott,
+    // bin width 2 * IQR * n^(-1/3), robust against outliers
+    FreedmanDiaconis,
+    // ceil(2 * n^(1/3)) bins
+    Rice,
+    // Sturges' rule corrected for the skewness of the data
+    Doane,
+    // ceil(sqrt(n)) bins
+    Sqrt,
+    // the number of bins maximizing Knuth's Bayesian posterior probability
+    Knuth,
+}
+
+// the largest number of bins considered by Knuth's rule
+const KNUTH_MAX_BINS: usize = 1000;
+
+// the largest number of bins given by the rules that choose a bin width, so
+// that a narrow spread with a far outlier can't ask for more bins than fit in
+// memory
+pub const WIDTH_MAX_BINS: usize = 100000;
+
+// bins_for_width converts a bin width into a number of bins covering range,
+// at most WIDTH_MAX_BINS
+fn bins_for_width(range: f64, width: f64) -> usize {
+    if !(width > 0.0 && range > 0.0) {
+        return 1;
+    }
+    let bins = (range / width).ceil();
+    if bins >= WIDTH_MAX_BINS as f64 {
+        WIDTH_MAX_BINS
+    } else {
+        (bins as usize).max(1)
+    }
+}
+
+// knuth_bins finds the number of equal width bins M that maximizes the log
+// posterior of K. H. Knuth, "Optimal Data-Based Binning for Histograms",
+// arXiv:physics/0605197, 2006:
+//
+// F(M) = n log(M) + lgamma(M/2) - M lgamma(1/2) - lgamma(n + M/2)
+//        + Sum_k lgamma(n_k + 1/2)
+//
+fn knuth_bins(sorted: &[f64]) -> usize {
+    let n = sorted.len();
+    let xmin = sorted[0];
+    let xmax = sorted[n - 1];
+
+    if xmax <= xmin {
+        return 1;
+    }
+
+    let lg_half = ln_gamma(0.5);
+    let mut best = 1;
+    let mut best_logp = f64::NEG_INFINITY;
+
+    for m in 1..n.min(KNUTH_MAX_BINS) + 1 {
+        let mf = m as f64;
+        let mut logp = n as f64 * mf.ln() + ln_gamma(mf / 2.0) - mf * lg_half -
+                       ln_gamma(n as f64 + mf / 2.0);
+
+        // count the sorted data between the bin edges, the last bin
+        // includes the maximum
+        let mut lower = 0;
+        for k in 1..m + 1 {
+            let upper = if k == m {
+                n
+            } else {
+                let edge = ((m - k) as f64 * xmin + k as f64 * xmax) / mf;
+                sorted.partition_point(|&x| x < edge)
+            };
+            logp += ln_gamma((upper - lower) as f64 + 0.5);
+            lower = upper;
+        }
+
+        if logp > best_logp {
+            best_logp = logp;
+            best = m;
+        }
+    }
+    best
+}
+
+// bin_count returns the number of bins that rule selects for the data,
+// which must not be empty. Scott and FreedmanDiaconis give at most
+// WIDTH_MAX_BINS bins.
+pub fn bin_count<T: F64, D: Data<T> + ?Sized>(data: &D, rule: BinRule) -> usize {
+    assert!(!data.is_empty(), "data must not be empty to choose the bins");
+    let n = data.len() as f64;
+    let (xmin, _, xmax, _) = minmax(data);
+    let range = xmax - xmin;
+
+    match rule {
+        BinRule::Sturges => n.log2().ceil() as usize + 1,
+        BinRule::Sqrt => n.sqrt().ceil() as usize,
+        BinRule::Rice => (2.0 * n.cbrt()).ceil() as usize,
+        BinRule::Scott => bins_for_width(range, 3.49 * sd(data) / n.cbrt()),
+        BinRule::FreedmanDiaconis => {
+            let sorted = sorted_f64(data);
+            let iqr = quantile_from_sorted_data(&sorted, 0.75) -
+                      quantile_from_sorted_data(&sorted, 0.25);
+            bins_for_width(range, 2.0 * iqr / n.cbrt())
+        }
+        BinRule::Doane => {
+            if data.len() < 3 {
+                return n.log2().ceil() as usize + 1;
+            }
+            let sigma_g1 = (6.0 * (n - 2.0) / ((n + 1.0) * (n + 3.0))).sqrt();
+            let g1 = skew(data);
+            let correction = if g1.is_finite() {
+                (1.0 + g1.abs() / sigma_g1).log2()
+            } else {
+                0.0
+            };
+            (1.0 + n.log2() + correction).ceil() as usize
+        }
+        BinRule::Knuth => knuth_bins(&sorted_f64(data)),
+    }
+}
+
+// bin_width returns the width of the bins that rule selects for the data
+pub fn bin_width<T: F64, D: Data<T> + ?Sized>(data: &D, rule: BinRule) -> f64 {
+    let (xmin, _, xmax, _) = minmax(data);
+    (xmax - xmin) / bin_count(data, rule) as f64
+}
+
+impl Histogram {
+    // from_data creates a histogram with uniform bins spanning the data, the
+    // number of bins chosen by rule, and increments it with every value. The
+    // upper edge is placed just above the maximum so that it is counted too.
+    // The data must not be empty.
+    pub fn from_data<T: F64, D: Data<T> + ?Sized>(data: &D, rule: BinRule) -> Histogram {
+        assert!(!data.is_empty(), "data must not be empty to choose the bins");
+        let (xmin, _, xmax, _) = minmax(data);
+        let n = bin_count(data, rule);
+
+        let mut h = Histogram::new_uniform(n, xmin, xmax.next_up());
+        for val in data.iter() {
+            // values outside the range can only be NaN
+            let _ = h.increment(val.f64());
+        }
+        h
+    }
+}
@@ -0,0 +1,390 @@
+// histogram2d.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Two-dimensional histograms, a port of gsl_histogram2d.
+//
+// The bins are stored row by row, bin (i, j) counting the pairs (x, y) with
+// xrange[i] <= x < xrange[i + 1] and yrange[j] <= y < yrange[j + 1].
+
+use histogram::{cumulative_sum, find_bin, make_uniform, Histogram, HistogramError};
+use types::F64;
+use {w_mean, wsd_with_fixed_mean};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram2d {
+    xrange: Vec<f64>,
+    yrange: Vec<f64>,
+    bin: Vec<f64>,
+}
+
+fn check_range(range: &[f64]) {
+    assert!(range.len() > 1, "at least two range values are needed");
+    for i in 1..range.len() {
+        assert!(range[i - 1] < range[i],
+                "histogram bin extremes must be in increasing order");
+    }
+}
+
+fn centers(range: &[f64]) -> Vec<f64> {
+    range.windows(2).map(|r| (r[0] + r[1]) / 2.0).collect()
+}
+
+impl Histogram2d {
+    // new_uniform creates a histogram of nx by ny bins of equal size covering
+    // [xmin, xmax) x [ymin, ymax)
+    pub fn new_uniform(nx: usize, ny: usize, xmin: f64, xmax: f64, ymin: f64, ymax: f64)
+                       -> Histogram2d {
+        assert!(nx > 0, "histogram length nx must be positive integer");
+        assert!(ny > 0, "histogram length ny must be positive integer");
+        assert!(xmin < xmax, "xmin must be less than xmax");
+        assert!(ymin < ymax, "ymin must be less than ymax");
+
+        Histogram2d {
+            xrange: make_uniform(nx, xmin, xmax),
+            yrange: make_uniform(ny, ymin, ymax),
+            bin: vec![0.0; nx * ny],
+        }
+    }
+
+    // with_ranges creates a histogram from user-defined bin edges in x and y
+    pub fn with_ranges<T: F64>(xrange: &[T], yrange: &[T]) -> Histogram2d {
+        let xrange: Vec<f64> = xrange.iter().map(|r| r.f64()).collect();
+        let yrange: Vec<f64> = yrange.iter().map(|r| r.f64()).collect();
+        check_range(&xrange);
+        check_range(&yrange);
+
+        let n = (xrange.len() - 1) * (yrange.len() - 1);
+        Histogram2d {
+            xrange,
+            yrange,
+            bin: vec![0.0; n],
+        }
+    }
+
+    // nx returns the number of bins in the x direction
+    pub fn nx(&self) -> usize {
+        self.xrange.len() - 1
+    }
+
+    // ny returns the number of bins in the y direction
+    pub fn ny(&self) -> usize {
+        self.yrange.len() - 1
+    }
+
+    // values returns the contents of the bins, row by row
+    pub fn values(&self) -> &[f64] {
+        &self.bin
+    }
+
+    // get returns the contents of bin (i, j)
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        assert!(i < self.nx() && j < self.ny(), "index lies outside valid range");
+        self.bin[i * self.ny() + j]
+    }
+
+    // x_range returns the lower and upper x edge of bin column i
+    pub fn x_range(&self, i: usize) -> (f64, f64) {
+        (self.xrange[i], self.xrange[i + 1])
+    }
+
+    // y_range returns the lower and upper y edge of bin row j
+    pub fn y_range(&self, j: usize) -> (f64, f64) {
+        (self.yrange[j], self.yrange[j + 1])
+    }
+
+    pub fn xmax(&self) -> f64 {
+        self.xrange[self.nx()]
+    }
+
+    pub fn xmin(&self) -> f64 {
+        self.xrange[0]
+    }
+
+    pub fn ymax(&self) -> f64 {
+        self.yrange[self.ny()]
+    }
+
+    pub fn ymin(&self) -> f64 {
+        self.yrange[0]
+    }
+
+    // reset sets all bins to zero
+    pub fn reset(&mut self) {
+        for b in &mut self.bin {
+            *b = 0.0;
+        }
+    }
+
+    // find returns the indices of the bin that contains (x, y)
+    pub fn find(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        match (find_bin(&self.xrange, x), find_bin(&self.yrange, y)) {
+            (Some(i), Some(j)) => Some((i, j)),
+            _ => None,
+        }
+    }
+
+    // increment adds one to the bin that contains (x, y)
+    pub fn increment(&mut self, x: f64, y: f64) -> Result<(), HistogramError> {
+        self.accumulate(x, y, 1.0)
+    }
+
+    // accumulate adds weight to the bin that contains (x, y)
+    pub fn accumulate(&mut self, x: f64, y: f64, weight: f64) -> Result<(), HistogramError> {
+        match self.find(x, y) {
+            Some((i, j)) => {
+                let ny = self.ny();
+                self.bin[i * ny + j] += weight;
+                Ok(())
+            }
+            None => Err(HistogramError::OutOfRange),
+        }
+    }
+
+    // max_val returns the largest value contained in the bins
+    pub fn max_val(&self) -> f64 {
+        let (i, j) = self.max_bin();
+        self.get(i, j)
+    }
+
+    // max_bin returns the indices of the first bin with the largest value
+    pub fn max_bin(&self) -> (usize, usize) {
+        let mut imax = 0;
+        for (k, &b) in self.bin.iter().enumerate() {
+            if b > self.bin[imax] {
+                imax = k;
+            }
+        }
+        (imax / self.ny(), imax % self.ny())
+    }
+
+    // min_val returns the smallest value contained in the bins
+    pub fn min_val(&self) -> f64 {
+        let (i, j) = self.min_bin();
+        self.get(i, j)
+    }
+
+    // min_bin returns the indices of the first bin with the smallest value
+    pub fn min_bin(&self) -> (usize, usize) {
+        let mut imin = 0;
+        for (k, &b) in self.bin.iter().enumerate() {
+            if b < self.bin[imin] {
+                imin = k;
+            }
+        }
+        (imin / self.ny(), imin % self.ny())
+    }
+
+    // x_weights returns the sum of the positive bins of every column
+    fn x_weights(&self) -> Vec<f64> {
+        self.bin
+            .chunks(self.ny())
+            .map(|row| row.iter().filter(|&&b| b > 0.0).sum())
+            .collect()
+    }
+
+    // y_weights returns the sum of the positive bins of every row
+    fn y_weights(&self) -> Vec<f64> {
+        let ny = self.ny();
+        let mut w = vec![0.0; ny];
+        for (k, &b) in self.bin.iter().enumerate() {
+            if b > 0.0 {
+                w[k % ny] += b;
+            }
+        }
+        w
+    }
+
+    // xmean returns the mean of the histogrammed x variable, where each bin
+    // is treated as a point at its center weighted by its value. Negative
+    // bins are ignored.
+    pub fn xmean(&self) -> f64 {
+        w_mean(&self.x_weights(), &centers(&self.xrange))
+    }
+
+    // ymean returns the mean of the histogrammed y variable
+    pub fn ymean(&self) -> f64 {
+        w_mean(&self.y_weights(), &centers(&self.yrange))
+    }
+
+    // xsigma returns the standard deviation of the histogrammed x variable
+    pub fn xsigma(&self) -> f64 {
+        let w = self.x_weights();
+        let xc = centers(&self.xrange);
+        let xmean = w_mean(&w, &xc);
+        wsd_with_fixed_mean(&w, &xc, xmean)
+    }
+
+    // ysigma returns the standard deviation of the histogrammed y variable
+    pub fn ysigma(&self) -> f64 {
+        let w = self.y_weights();
+        let yc = centers(&self.yrange);
+        let ymean = w_mean(&w, &yc);
+        wsd_with_fixed_mean(&w, &yc, ymean)
+    }
+
+    // cov returns the covariance of the histogrammed x and y variables.
+    // Negative bins are ignored.
+    pub fn cov(&self) -> f64 {
+        let xmean = self.xmean();
+        let ymean = self.ymean();
+        let xc = centers(&self.xrange);
+        let yc = centers(&self.yrange);
+        let ny = self.ny();
+
+        let mut wcovariance = 0.0;
+        let mut weight = 0.0;
+
+        for (k, &wij) in self.bin.iter().enumerate() {
+            if wij > 0.0 {
+                let dx = xc[k / ny] - xmean;
+                let dy = yc[k % ny] - ymean;
+                weight += wij;
+                wcovariance += (dx * dy - wcovariance) * (wij / weight);
+            }
+        }
+        wcovariance
+    }
+
+    // sum returns the sum of all bin values, including negative ones
+    pub fn sum(&self) -> f64 {
+        self.bin.iter().sum()
+    }
+
+    // x_projection returns the marginal histogram of x, summing every column
+    pub fn x_projection(&self) -> Histogram {
+        let bin = self.bin.chunks(self.ny()).map(|row| row.iter().sum()).collect();
+        Histogram::from_parts(self.xrange.clone(), bin)
+    }
+
+    // y_projection returns the marginal histogram of y, summing every row
+    pub fn y_projection(&self) -> Histogram {
+        let ny = self.ny();
+        let mut bin = vec![0.0; ny];
+        for (k, &b) in self.bin.iter().enumerate() {
+            bin[k % ny] += b;
+        }
+        Histogram::from_parts(self.yrange.clone(), bin)
+    }
+
+    // equal_bins returns true if both histograms have identical bin ranges
+    pub fn equal_bins(&self, other: &Histogram2d) -> bool {
+        self.xrange == other.xrange && self.yrange == other.yrange
+    }
+
+    fn check_bins(&self, other: &Histogram2d) -> Result<(), HistogramError> {
+        if self.equal_bins(other) {
+            Ok(())
+        } else {
+            Err(HistogramError::Mismatch)
+        }
+    }
+
+    // add adds the contents of the bins of other to this histogram
+    pub fn add(&mut self, other: &Histogram2d) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b += *o;
+        }
+        Ok(())
+    }
+
+    // sub subtracts the contents of the bins of other from this histogram
+    pub fn sub(&mut self, other: &Histogram2d) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b -= *o;
+        }
+        Ok(())
+    }
+
+    // mul multiplies the contents of the bins by the contents of other
+    pub fn mul(&mut self, other: &Histogram2d) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b *= *o;
+        }
+        Ok(())
+    }
+
+    // div divides the contents of the bins by the contents of other
+    pub fn div(&mut self, other: &Histogram2d) -> Result<(), HistogramError> {
+        self.check_bins(other)?;
+        for (b, o) in self.bin.iter_mut().zip(&other.bin) {
+            *b /= *o;
+        }
+        Ok(())
+    }
+
+    // scale multiplies the contents of the bins by the constant scale
+    pub fn scale(&mut self, scale: f64) {
+        for b in &mut self.bin {
+            *b *= scale;
+        }
+    }
+
+    // shift adds the constant offset to the contents of the bins
+    pub fn shift(&mut self, offset: f64) {
+        for b in &mut self.bin {
+            *b += offset;
+        }
+    }
+}
+
+// pdf
+
+// Histogram2dPdf is the probability distribution described by a histogram,
+// a port of gsl_histogram2d_pdf. The bins are treated as one cumulative
+// distribution, walking the bins row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram2dPdf {
+    xrange: Vec<f64>,
+    yrange: Vec<f64>,
+    sum: Vec<f64>,
+}
+
+impl Histogram2dPdf {
+    // new creates the distribution from a histogram with non-negative bins
+    pub fn new(h: &Histogram2d) -> Result<Histogram2dPdf, HistogramError> {
+        Ok(Histogram2dPdf {
+            xrange: h.xrange.clone(),
+            yrange: h.yrange.clone(),
+            sum: cumulative_sum(&h.bin)?,
+        })
+    }
+
+    // sample returns the pair (x, y) drawn from the distribution for the
+    // uniform random numbers r1 and r2 in [0, 1]. r1 selects the bin and the
+    // position in x within it, r2 the position in y.
+    pub fn sample(&self, r1: f64, r2: f64) -> (f64, f64) {
+        // wrap the exclusive top of the bin down to the inclusive bottom
+        let r1 = if r1 == 1.0 { 0.0 } else { r1 };
+        let r2 = if r2 == 1.0 { 0.0 } else { r2 };
+
+        let k = find_bin(&self.sum, r1).expect("r1 must lie in [0, 1]");
+        let ny = self.yrange.len() - 1;
+        let i = k / ny;
+        let j = k % ny;
+
+        let delta = (r1 - self.sum[k]) / (self.sum[k + 1] - self.sum[k]);
+        let x = self.xrange[i] + delta * (self.xrange[i + 1] - self.xrange[i]);
+        let y = self.yrange[j] + r2 * (self.yrange[j + 1] - self.yrange[j]);
+        (x, y)
+    }
+}
@@ -0,0 +1,136 @@
+// integer.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Exact statistics of integer data.
+//
+// The functions at the crate root convert every value to f64, which rounds
+// integers beyond 2^53, and then run a floating point recurrence. The
+// functions here accumulate the sums in i128 and divide only at the end, so
+// the sum is exact and the mean and tss are the exact values rounded once to
+// f64:
+//
+//     let total = integer::sum(&bytes).unwrap();
+//     let m = integer::mean(&bytes).unwrap();
+//
+// Every function returns None if a sum overflows i128. The sums of squares
+// are taken about the first value, so data clustered far from zero, like
+// timestamps, doesn't overflow.
+
+use data::Data;
+
+// Integer converts the values of a dataset to i128 without loss
+pub trait Integer {
+    fn i128(&self) -> i128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn i128(&self) -> i128 {
+                    *self as i128
+                }
+            }
+        )*
+    }
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl<T: Integer + ?Sized> Integer for &T {
+    fn i128(&self) -> i128 {
+        (**self).i128()
+    }
+}
+
+fn bits(x: u128) -> i32 {
+    (128 - x.leading_zeros()) as i32
+}
+
+// div_round returns p / q correctly rounded to f64, for 0 < q < 2^64
+fn div_round(p: i128, q: u128) -> f64 {
+    // scale |p| so that the quotient has at least 56 bits, the bits beyond
+    // the 53 of an f64 deciding the rounding
+    let k = (56 + bits(q) - bits(p.unsigned_abs())).max(0);
+    let a = p.unsigned_abs() << k;
+    let (quot, rem) = (a / q, a % q);
+
+    // a nonzero remainder sets the lowest bit, so that a quotient just above
+    // a tie isn't rounded as the tie
+    let x = (quot | (rem != 0) as u128) as f64 * 2f64.powi(-k);
+    if p < 0 { -x } else { x }
+}
+
+// sum
+
+// sum returns the exact sum of the values
+pub fn sum<T: Integer, D: Data<T> + ?Sized>(data: &D) -> Option<i128> {
+    let mut sum: i128 = 0;
+    for val in data.iter() {
+        sum = sum.checked_add(val.i128())?;
+    }
+    Some(sum)
+}
+
+// mean
+
+// mean returns the exact mean rounded to f64, NaN for no values
+pub fn mean<T: Integer, D: Data<T> + ?Sized>(data: &D) -> Option<f64> {
+    if data.is_empty() {
+        return Some(f64::NAN);
+    }
+    Some(div_round(sum(data)?, data.len() as u128))
+}
+
+// tss
+
+// tss_numerator returns n times the sum of squares about the mean, which is
+// an integer, from the deviations d from the first value:
+// n tss = n sum(d^2) - sum(d)^2
+fn tss_numerator<T: Integer, D: Data<T> + ?Sized>(data: &D) -> Option<i128> {
+    let x0 = data.at(0).i128();
+    let mut sum: i128 = 0;
+    let mut sum2: i128 = 0;
+    for val in data.iter() {
+        let d = val.i128().checked_sub(x0)?;
+        sum = sum.checked_add(d)?;
+        sum2 = sum2.checked_add(d.checked_mul(d)?)?;
+    }
+    (data.len() as i128).checked_mul(sum2)?.checked_sub(sum.checked_mul(sum)?)
+}
+
+// tss returns the exact sum of squares about the mean rounded to f64
+pub fn tss<T: Integer, D: Data<T> + ?Sized>(data: &D) -> Option<f64> {
+    if data.is_empty() {
+        return Some(0.0);
+    }
+    Some(div_round(tss_numerator(data)?, data.len() as u128))
+}
+
+// variance
+
+// variance returns the sample variance, the exact tss divided by n - 1, so
+// rounded twice
+pub fn variance<T: Integer, D: Data<T> + ?Sized>(data: &D) -> Option<f64> {
+    Some(tss(data)? / (data.len() as f64 - 1.0))
+}
+
+pub fn sd<T: Integer, D: Data<T> + ?Sized>(data: &D) -> Option<f64> {
+    Some(variance(data)?.sqrt())
+}
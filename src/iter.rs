@@ -0,0 +1,207 @@
+// iter.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Statistics of iterators, for data that isn't in a slice, like the values
+// of a map or numbers parsed from a file:
+//
+//     let m = deque.iter().cloned().mean();
+//     let s = map.values().cloned().stats();
+//
+// The statistics are found in a single pass with the updates of
+//
+// P. Pébay, "Formulas for Robust, One-Pass Parallel Computation of
+// Covariances and Arbitrary-Order Statistical Moments", Sandia Report
+// SAND2008-6212, 2008.
+//
+// which extend the recurrence of B. P. Welford used by correlation to the
+// third and fourth central moments. The results agree with the slice
+// functions up to rounding.
+
+use types::F64;
+
+// RunningStats accumulates the moments and extremes of a stream of values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    n: usize,
+    mean: f64,
+    // the sums of the second, third and fourth powers of the deviations
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    min_index: usize,
+    max: f64,
+    max_index: usize,
+}
+
+impl Default for RunningStats {
+    fn default() -> RunningStats {
+        RunningStats::new()
+    }
+}
+
+impl RunningStats {
+    pub fn new() -> RunningStats {
+        RunningStats {
+            n: 0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            min: f64::NAN,
+            min_index: 0,
+            max: f64::NAN,
+            max_index: 0,
+        }
+    }
+
+    // add adds the next value
+    pub fn add<T: F64>(&mut self, x: T) {
+        let x = x.f64();
+        let n1 = self.n as f64;
+        let n = n1 + 1.0;
+
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * n1;
+
+        self.mean += delta_n;
+        self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2 -
+                   4.0 * delta_n * self.m3;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+
+        // like min and max the first NaN is kept
+        if self.n == 0 || x < self.min || (x.is_nan() && !self.min.is_nan()) {
+            self.min = x;
+            self.min_index = self.n;
+        }
+        if self.n == 0 || x > self.max || (x.is_nan() && !self.max.is_nan()) {
+            self.max = x;
+            self.max_index = self.n;
+        }
+
+        self.n += 1;
+    }
+
+    // len returns the number of values added
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    // mean returns the arithmetic mean, zero if no values were added
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    // variance returns the sample variance, with n - 1 in the denominator
+    pub fn variance(&self) -> f64 {
+        self.m2 / (self.n as f64 - 1.0)
+    }
+
+    pub fn sd(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    // skew returns the skewness, normalized by the sample sd like skew
+    pub fn skew(&self) -> f64 {
+        let sd = self.sd();
+        self.m3 / self.n as f64 / (sd * sd * sd)
+    }
+
+    // kurtosis returns the excess kurtosis, normalized by the sample sd like
+    // kurtosis
+    pub fn kurtosis(&self) -> f64 {
+        let variance = self.variance();
+        self.m4 / self.n as f64 / (variance * variance) - 3.0
+    }
+
+    // min returns the first smallest value and its position, or None if no
+    // values were added
+    pub fn min(&self) -> Option<(f64, usize)> {
+        if self.n == 0 {
+            None
+        } else {
+            Some((self.min, self.min_index))
+        }
+    }
+
+    // max returns the first largest value and its position, or None if no
+    // values were added
+    pub fn max(&self) -> Option<(f64, usize)> {
+        if self.n == 0 {
+            None
+        } else {
+            Some((self.max, self.max_index))
+        }
+    }
+}
+
+// StatsIterExt adds statistics to every iterator over F64 values. Every
+// method consumes the iterator, so use stats to get several statistics from
+// one pass.
+pub trait StatsIterExt: Iterator + Sized
+    where Self::Item: F64
+{
+    // stats collects the moments and extremes of the values
+    fn stats(self) -> RunningStats {
+        let mut stats = RunningStats::new();
+        for x in self {
+            stats.add(x);
+        }
+        stats
+    }
+
+    fn mean(self) -> f64 {
+        self.stats().mean()
+    }
+
+    fn variance(self) -> f64 {
+        self.stats().variance()
+    }
+
+    fn sd(self) -> f64 {
+        self.stats().sd()
+    }
+
+    fn skew(self) -> f64 {
+        self.stats().skew()
+    }
+
+    fn kurtosis(self) -> f64 {
+        self.stats().kurtosis()
+    }
+
+    // minmax returns the first smallest and largest values and their
+    // positions, or None for an empty iterator
+    fn minmax(self) -> Option<(f64, usize, f64, usize)> {
+        let stats = self.stats();
+        match (stats.min(), stats.max()) {
+            (Some((min, i)), Some((max, j))) => Some((min, i, max, j)),
+            _ => None,
+        }
+    }
+}
+
+impl<I: Iterator> StatsIterExt for I where I::Item: F64 {}
@@ -0,0 +1,123 @@
+// jackknife.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Jackknife estimates of the bias and the variance of a statistic, from the
+// values of the statistic with one observation left out.
+//
+// For the statistic t of n values, t_i without value i and t_mean the mean
+// of the t_i:
+//
+// bias      = (n - 1) (t_mean - t)
+// std_error = sqrt((n - 1)/n Sum (t_i - t_mean)^2)
+//
+
+use data::Data;
+use types::F64;
+use {mean, tss_mean};
+
+// Jackknife holds the leave-one-out values of a statistic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jackknife {
+    // the statistic of the original data
+    pub estimate: f64,
+    // the statistic with value i left out, for every i
+    pub leave_one_out: Vec<f64>,
+    // the jackknife estimate of the bias
+    pub bias: f64,
+    // the bias-corrected estimate, estimate - bias
+    pub corrected: f64,
+    // the jackknife standard error
+    pub std_error: f64,
+}
+
+impl Jackknife {
+    fn new(estimate: f64, leave_one_out: Vec<f64>) -> Jackknife {
+        let n = leave_one_out.len() as f64;
+        let loo_mean = mean(&leave_one_out);
+        let bias = (n - 1.0) * (loo_mean - estimate);
+        let std_error = ((n - 1.0) / n * tss_mean(&leave_one_out, loo_mean)).sqrt();
+
+        Jackknife {
+            estimate,
+            leave_one_out,
+            bias,
+            corrected: estimate - bias,
+            std_error,
+        }
+    }
+}
+
+// jackknife calculates the statistic n times, leaving out every value once
+pub fn jackknife<T, D, F>(data: &D, statistic: F) -> Jackknife
+    where T: F64,
+          D: Data<T> + ?Sized,
+          F: Fn(&[f64]) -> f64
+{
+    assert!(data.len() > 1, "the jackknife needs at least two values");
+
+    let data: Vec<f64> = data.iter().map(|x| x.f64()).collect();
+    let estimate = statistic(&data);
+    Jackknife::new(estimate, leave_one_out(&data, &statistic))
+}
+
+pub(crate) fn leave_one_out<F: Fn(&[f64]) -> f64>(data: &[f64], statistic: &F) -> Vec<f64> {
+    let n = data.len();
+    let mut sample = Vec::with_capacity(n - 1);
+    let mut loo = Vec::with_capacity(n);
+    for i in 0..n {
+        sample.clear();
+        sample.extend_from_slice(&data[..i]);
+        sample.extend_from_slice(&data[i + 1..]);
+        loo.push(statistic(&sample));
+    }
+    loo
+}
+
+// jackknife_mean is jackknife(data, mean) in O(n), using the leave-one-out
+// mean t_i = t + (t - x_i)/(n - 1)
+pub fn jackknife_mean<T: F64, D: Data<T> + ?Sized>(data: &D) -> Jackknife {
+    assert!(data.len() > 1, "the jackknife needs at least two values");
+
+    let n = data.len() as f64;
+    let m = mean(data);
+    let loo = data.iter().map(|x| m + (m - x.f64()) / (n - 1.0)).collect();
+    Jackknife::new(m, loo)
+}
+
+// jackknife_variance is jackknife(data, variance) in O(n). Removing x_i
+// from the sum of squares S about the mean m gives
+//
+// S_i = S - (x_i - m) (x_i - m_i)
+//
+// with m_i the leave-one-out mean, and the variance S_i / (n - 2).
+pub fn jackknife_variance<T: F64, D: Data<T> + ?Sized>(data: &D) -> Jackknife {
+    assert!(data.len() > 2, "the jackknife of the variance needs at least three values");
+
+    let n = data.len() as f64;
+    let m = mean(data);
+    let tss = tss_mean(data, m);
+    let loo = data.iter()
+        .map(|x| {
+            let xi = x.f64();
+            let mi = m + (m - xi) / (n - 1.0);
+            (tss - (xi - m) * (xi - mi)) / (n - 2.0)
+        })
+        .collect();
+    Jackknife::new(tss / (n - 1.0), loo)
+}
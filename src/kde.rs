@@ -0,0 +1,210 @@
+// kde.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Kernel density estimation.
+//
+// The density is evaluated on an equally spaced grid. The data is linearly
+// binned onto the grid and the binned counts are convolved with the kernel
+// using the FFT, which takes O(n + m log m) for n values and m grid points
+// instead of O(n m) for direct summation. See:
+//
+// B. W. Silverman, "Algorithm AS 176: Kernel Density Estimation Using the
+// Fast Fourier Transform", Applied Statistics, Vol 31, No 1, 1982.
+//
+
+use std::f64::consts::PI;
+
+use data::Data;
+use types::F64;
+use {minmax, quantile_from_sorted_data, sd, sorted_f64};
+
+// the number of grid points used by kde
+const KDE_GRID_POINTS: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    Gaussian,
+    Epanechnikov,
+    Triangular,
+    Uniform,
+}
+
+impl Kernel {
+    // eval returns the value of the kernel, scaled to unit bandwidth, at u
+    pub fn eval(&self, u: f64) -> f64 {
+        match *self {
+            Kernel::Gaussian => (-0.5 * u * u).exp() / (2.0 * PI).sqrt(),
+            Kernel::Epanechnikov if u.abs() <= 1.0 => 0.75 * (1.0 - u * u),
+            Kernel::Triangular if u.abs() <= 1.0 => 1.0 - u.abs(),
+            Kernel::Uniform if u.abs() <= 1.0 => 0.5,
+            _ => 0.0,
+        }
+    }
+
+    // support returns how many bandwidths the kernel reaches from its center.
+    // For the gaussian kernel three bandwidths are used, like R's density.
+    fn support(&self) -> f64 {
+        match *self {
+            Kernel::Gaussian => 3.0,
+            _ => 1.0,
+        }
+    }
+}
+
+// Kde holds the estimated density at the grid points x
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kde {
+    pub x: Vec<f64>,
+    pub density: Vec<f64>,
+}
+
+// kde estimates the density of the data with the kernel and bandwidth on a
+// grid of 512 points that extends past the data as far as the kernel reaches
+pub fn kde<T: F64, D: Data<T> + ?Sized>(data: &D, kernel: Kernel, bandwidth: f64) -> Kde {
+    let (min, _, max, _) = minmax(data);
+    let cut = kernel.support() * bandwidth;
+    kde_grid(data, kernel, bandwidth, min - cut, max + cut, KDE_GRID_POINTS)
+}
+
+// kde_grid estimates the density of the data with the kernel and bandwidth
+// on a grid of n points from xmin to xmax. Data outside the grid is ignored.
+pub fn kde_grid<T, D>(data: &D,
+                      kernel: Kernel,
+                      bandwidth: f64,
+                      xmin: f64,
+                      xmax: f64,
+                      n: usize)
+                      -> Kde
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    assert!(bandwidth > 0.0, "bandwidth must be positive");
+    assert!(xmin < xmax, "xmin must be less than xmax");
+    assert!(n > 1, "the grid needs at least two points");
+
+    let delta = (xmax - xmin) / (n - 1) as f64;
+    let x: Vec<f64> = (0..n).map(|i| xmin + i as f64 * delta).collect();
+
+    // linear binning, each value is shared between its two neighbouring grid
+    // points in proportion to its distance to them
+    let size = (2 * n).next_power_of_two();
+    let mut counts = vec![(0.0, 0.0); size];
+    for val in data.iter() {
+        let t = (val.f64() - xmin) / delta;
+        if t >= 0.0 && t <= (n - 1) as f64 {
+            let j = t.floor() as usize;
+            let frac = t - j as f64;
+            counts[j].0 += 1.0 - frac;
+            if frac > 0.0 {
+                counts[j + 1].0 += frac;
+            }
+        }
+    }
+
+    // the kernel at all grid offsets, negative offsets wrapped around
+    let mut weights = vec![(0.0, 0.0); size];
+    for l in 0..n {
+        let k = kernel.eval(l as f64 * delta / bandwidth);
+        weights[l].0 = k;
+        if l > 0 {
+            weights[size - l].0 = k;
+        }
+    }
+
+    fft(&mut counts, false);
+    fft(&mut weights, false);
+    for (c, w) in counts.iter_mut().zip(&weights) {
+        *c = (c.0 * w.0 - c.1 * w.1, c.0 * w.1 + c.1 * w.0);
+    }
+    fft(&mut counts, true);
+
+    let scale = 1.0 / (data.len() as f64 * bandwidth * size as f64);
+    let density = counts[..n].iter().map(|c| (c.0 * scale).max(0.0)).collect();
+
+    Kde { x, density }
+}
+
+// fft performs an in-place radix-2 Cooley-Tukey transform of the complex
+// values (re, im), whose number must be a power of two. The inverse
+// transform is not scaled by 1/n.
+fn fft(a: &mut [(f64, f64)], inverse: bool) {
+    let n = a.len();
+
+    // bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (wr, wi) = ((angle * k as f64).cos(), (angle * k as f64).sin());
+                let (ur, ui) = a[start + k];
+                let (vr, vi) = a[start + k + len / 2];
+                let (tr, ti) = (vr * wr - vi * wi, vr * wi + vi * wr);
+                a[start + k] = (ur + tr, ui + ti);
+                a[start + k + len / 2] = (ur - tr, ui - ti);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+// spread returns min(sd, IQR / 1.349), the scale estimate used by the
+// rules of thumb; 1.349 is the IQR of a standard normal distribution. Like
+// R's bw.nrd0 it falls back to the sd if the IQR is zero, and to the size of
+// the first value, or 1, if the data is constant, so that the bandwidth is
+// always positive.
+fn spread<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
+    let sorted = sorted_f64(data);
+    let iqr = quantile_from_sorted_data(&sorted, 0.75) - quantile_from_sorted_data(&sorted, 0.25);
+    let sd = sd(data);
+    let lo = if iqr > 0.0 { sd.min(iqr / 1.349) } else { sd };
+    if lo > 0.0 {
+        lo
+    } else if data.at(0).f64() != 0.0 {
+        data.at(0).f64().abs()
+    } else {
+        1.0
+    }
+}
+
+// silverman_bandwidth returns Silverman's rule of thumb bandwidth
+// 0.9 * min(sd, IQR / 1.349) * n^(-1/5)
+pub fn silverman_bandwidth<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
+    0.9 * spread(data) * (data.len() as f64).powf(-0.2)
+}
+
+// scott_bandwidth returns Scott's rule of thumb bandwidth
+// 1.059 * min(sd, IQR / 1.349) * n^(-1/5)
+pub fn scott_bandwidth<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
+    1.059 * spread(data) * (data.len() as f64).powf(-0.2)
+}
@@ -18,13 +18,37 @@
 // Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
 //
 
+pub mod accuracy;
+pub mod bootstrap;
+pub mod data;
+pub mod filter;
+pub mod float;
+pub mod histogram;
+pub mod histogram2d;
+pub mod integer;
+pub mod iter;
+pub mod jackknife;
+pub mod kde;
+pub mod missing;
+pub mod nan;
+pub mod outliers;
+pub mod permutation;
+pub mod randist;
+pub mod rng;
+mod specfunc;
+pub mod statistics;
+pub mod summary;
 pub mod types;
+
+use std::cmp::Ordering::Equal;
+use accuracy::{two_product, two_sum, Accuracy, ExactSum, NeumaierSum};
+use data::Data;
 use types::F64;
 
 // mean
 
 // mean calculates the arithmetic mean with the recurrence relation
-pub fn mean<T: F64>(data: &[T]) -> f64 {
+pub fn mean<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mut mean = 0.0;
     for (i, val) in data.iter().enumerate() {
         mean += (val.f64() - mean) / (i + 1) as f64;
@@ -32,17 +56,40 @@ pub fn mean<T: F64>(data: &[T]) -> f64 {
     mean
 }
 
+// mean_with is mean with the given accuracy
+pub fn mean_with<T: F64, D: Data<T> + ?Sized>(data: &D, accuracy: Accuracy) -> f64 {
+    match accuracy {
+        Accuracy::Fast => mean(data),
+        Accuracy::Compensated => {
+            let mut sum = NeumaierSum::new();
+            for val in data.iter() {
+                sum.add(val.f64());
+            }
+            sum.sum() / data.len() as f64
+        }
+        Accuracy::Exact => {
+            let mut sum = ExactSum::new();
+            for val in data.iter() {
+                sum.add(val.f64());
+            }
+            let mut n = ExactSum::new();
+            n.add(data.len() as f64);
+            sum.div(&n)
+        }
+    }
+}
+
 // absdev
 
-pub fn absdev<T: F64>(data: &[T]) -> f64 {
+pub fn absdev<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     absdev_mean(data, mean(data))
 }
 
 // absdev_mean finds the absolute deviation of the data interface
-pub fn absdev_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+pub fn absdev_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     let mut sum = 0.0;
     // the sum of the absolute deviations
-    for val in data {
+    for val in data.iter() {
         sum += (val.f64() - mean).abs();
     }
     sum / data.len() as f64
@@ -51,24 +98,36 @@ pub fn absdev_mean<T: F64>(data: &[T], mean: f64) -> f64 {
 // covariance
 
 // takes a dataset and calculates the covariance
-fn covariance_nonpub<T: F64>(data1: &[T], data2: &[T], mean1: f64, mean2: f64) -> f64 {
+fn covariance_nonpub<T, D1, D2>(data1: &D1, data2: &D2, mean1: f64, mean2: f64) -> f64
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
     let mut res = 0.0;
     // calculate the sum of the squares
     for i in 0..data1.len() {
-        let delta1 = data1[i].f64() - mean1;
-        let delta2 = data2[i].f64() - mean2;
+        let delta1 = data1.at(i).f64() - mean1;
+        let delta2 = data2.at(i).f64() - mean2;
         res += (delta1 * delta2 - res) / (i + 1) as f64;
     }
     res
 }
 
-pub fn covariance_mean<T: F64>(data1: &[T], data2: &[T], mean1: f64, mean2: f64) -> f64 {
+pub fn covariance_mean<T, D1, D2>(data1: &D1, data2: &D2, mean1: f64, mean2: f64) -> f64
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
     let n = data1.len();
     let covariance = covariance_nonpub(data1, data2, mean1, mean2);
     covariance * (n as f64) / (n - 1) as f64
 }
 
-pub fn covariance<T: F64>(data1: &[T], data2: &[T]) -> f64 {
+pub fn covariance<T, D1, D2>(data1: &D1, data2: &D2) -> f64
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
     let mean1 = mean(data1);
     let mean2 = mean(data2);
     covariance_mean(data1, data2, mean1, mean2)
@@ -91,7 +150,11 @@ pub fn covariance<T: F64>(data1: &[T], data2: &[T]) -> f64 {
 //
 // S_n = S_{n-1} + ((n-1)/n) * (x_n - mu_x_{n-1}) * (y_n - mu_y_{n-1})
 //
-pub fn correlation<T: F64>(data1: &[T], data2: &[T]) -> f64 {
+pub fn correlation<T, D1, D2>(data1: &D1, data2: &D2) -> f64
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
     let mut sum_xsq = 0.0;
     let mut sum_ysq = 0.0;
     let mut sum_cross = 0.0;
@@ -102,13 +165,13 @@ pub fn correlation<T: F64>(data1: &[T], data2: &[T]) -> f64 {
     // sum_cross = Sum [ (x_i - mu_x) * (y_i - mu_y) ]
     // using the above relation from Welford's paper
 
-    let mut mean_x = data1[0].f64();
-    let mut mean_y = data2[0].f64();
+    let mut mean_x = data1.at(0).f64();
+    let mut mean_y = data2.at(0).f64();
 
     for i in 1..data1.len() {
         let ratio = i as f64 / (i + 1) as f64;
-        let delta_x = data1[i].f64() - mean_x;
-        let delta_y = data2[i].f64() - mean_y;
+        let delta_x = data1.at(i).f64() - mean_x;
+        let delta_y = data2.at(i).f64() - mean_y;
         sum_xsq += delta_x * delta_x * ratio;
         sum_ysq += delta_y * delta_y * ratio;
         sum_cross += delta_x * delta_y * ratio;
@@ -121,13 +184,13 @@ pub fn correlation<T: F64>(data1: &[T], data2: &[T]) -> f64 {
 
 // kurtosis
 
-pub fn kurtosis<T: F64>(data: &[T]) -> f64 {
+pub fn kurtosis<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mean = mean(data);
     let est_sd = sd_mean(data, mean);
     kurtosis_main_sd(data, mean, est_sd)
 }
 
-pub fn kurtosis_main_sd<T: F64>(data: &[T], mean: f64, sd: f64) -> f64 {
+pub fn kurtosis_main_sd<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64, sd: f64) -> f64 {
     let mut avg = 0.0;
 
     // calculate the fourth moment the deviations, normalized by the sd
@@ -136,7 +199,7 @@ pub fn kurtosis_main_sd<T: F64>(data: &[T], mean: f64, sd: f64) -> f64 {
     // there aren't any large sums that can overflow
 
     for i in 0..data.len() {
-        let x = (data[i].f64() - mean) / sd;
+        let x = (data.at(i).f64() - mean) / sd;
         avg += (x * x * x * x - avg) / (i + 1) as f64;
     }
     avg - 3.0 // makes kurtosis zero for a Gaussian
@@ -144,29 +207,47 @@ pub fn kurtosis_main_sd<T: F64>(data: &[T], mean: f64, sd: f64) -> f64 {
 
 // lag-1
 
-pub fn lag1autocorrelation<T: F64>(data: &[T]) -> f64 {
+pub fn lag1autocorrelation<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mean = mean(data);
     return lag1autocorrelation_mean(data, mean);
 }
 
-pub fn lag1autocorrelation_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+pub fn lag1autocorrelation_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     let mut q = 0.0;
-    let mut v = (data[0].f64() - mean) * (data[0].f64() - mean);
+    let mut v = (data.at(0).f64() - mean) * (data.at(0).f64() - mean);
 
     for i in 1..data.len() {
-        let delta0 = data[i - 1].f64() - mean;
-        let delta1 = data[i].f64() - mean;
+        let delta0 = data.at(i - 1).f64() - mean;
+        let delta1 = data.at(i).f64() - mean;
         q += (delta0 * delta1 - q) / (i + 1) as f64;
         v += (delta1 * delta1 - v) / (i + 1) as f64;
     }
     q / v // r1
 }
 
+// mad
+
+// mad0 calculates the median absolute deviation from the median,
+// median(|x_i - median(x)|), like gsl_stats_mad0
+pub fn mad0<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
+    let sorted = sorted_f64(data);
+    let median = median_from_sorted_data(&sorted);
+    let deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    median_from_sorted_data(&sorted_f64(&deviations))
+}
+
+// mad calculates the median absolute deviation scaled by 1.4826, which makes
+// it a consistent estimate of the standard deviation of Gaussian data, like
+// gsl_stats_mad
+pub fn mad<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
+    1.482602218505602 * mad0(data)
+}
+
 // median
 
 // MedianFromSortedData calculates the median of the sorted data.
 // Note that the function doesn't check wheather the data is actually sorted.
-pub fn median_from_sorted_data<T: F64>(sorted_data: &[T]) -> f64 {
+pub fn median_from_sorted_data<T: F64, D: Data<T> + ?Sized>(sorted_data: &D) -> f64 {
     let len = sorted_data.len();
     let lhs = (len - 1) / 2;
     let rhs = len / 2;
@@ -176,17 +257,17 @@ pub fn median_from_sorted_data<T: F64>(sorted_data: &[T]) -> f64 {
     }
 
     if lhs == rhs {
-        sorted_data[lhs].f64()
+        sorted_data.at(lhs).f64()
     } else {
-        (sorted_data[lhs].f64() + sorted_data[rhs].f64()) / 2.0
+        (sorted_data.at(lhs).f64() + sorted_data.at(rhs).f64()) / 2.0
     }
 }
 
 // minmax
 
 // Max finds the first largest member and the members position within the data
-pub fn max<T: F64>(data: &[T]) -> (f64, usize) {
-    let mut max = data[0].f64();
+pub fn max<T: F64, D: Data<T> + ?Sized>(data: &D) -> (f64, usize) {
+    let mut max = data.at(0).f64();
     let mut max_index = 0;
 
     for (i, val) in data.iter().enumerate() {
@@ -207,8 +288,8 @@ pub fn max<T: F64>(data: &[T]) -> (f64, usize) {
 }
 
 // Min finds the first smallest member and the members position within the data
-pub fn min<T: F64>(data: &[T]) -> (f64, usize) {
-    let mut min = data[0].f64();
+pub fn min<T: F64, D: Data<T> + ?Sized>(data: &D) -> (f64, usize) {
+    let mut min = data.at(0).f64();
     let mut min_index = 0;
 
     for (i, val) in data.iter().enumerate() {
@@ -230,14 +311,14 @@ pub fn min<T: F64>(data: &[T]) -> (f64, usize) {
 
 // Minmax finds the first smallest and largest members and
 // the members positions within the data
-pub fn minmax<T: F64>(data: &[T]) -> (f64, u32, f64, u32) {
+pub fn minmax<T: F64, D: Data<T> + ?Sized>(data: &D) -> (f64, u32, f64, u32) {
     let mut min_index: u32 = 0;
     let mut max_index: u32 = 0;
-    let mut min = data[0].f64();
-    let mut max = data[0].f64();
+    let mut min = data.at(0).f64();
+    let mut max = data.at(0).f64();
 
     for i in 0..data.len() {
-        let xi = data[i].f64();
+        let xi = data.at(i).f64();
 
         if xi < min {
             min = xi;
@@ -263,7 +344,11 @@ pub fn minmax<T: F64>(data: &[T]) -> (f64, u32, f64, u32) {
 // pvariance
 
 // p_variance finds the pooled variance of two datasets
-pub fn p_variance<T: F64>(data1: &[T], data2: &[T]) -> f64 {
+pub fn p_variance<T, D1, D2>(data1: &D1, data2: &D2) -> f64
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
     let n1 = data1.len();
     let n2 = data2.len();
 
@@ -278,7 +363,7 @@ pub fn p_variance<T: F64>(data1: &[T], data2: &[T]) -> f64 {
 // QuantileFromSortedData performs the quantile function, also called percent
 // point function or inverse cumulative distribution function, on the sorted data.
 // Note that the function doesn't check wheather the data is actually sorted.
-pub fn quantile_from_sorted_data<T: F64>(sorted_data: &[T], f: f64) -> f64 {
+pub fn quantile_from_sorted_data<T: F64, D: Data<T> + ?Sized>(sorted_data: &D, f: f64) -> f64 {
     let n = sorted_data.len() as i32;
     let index = f * (n - 1) as f64;
     let lhs = index as i32;
@@ -289,23 +374,31 @@ pub fn quantile_from_sorted_data<T: F64>(sorted_data: &[T], f: f64) -> f64 {
     }
 
     if lhs == n - 1 {
-        return sorted_data[lhs as usize].f64();
+        return sorted_data.at(lhs as usize).f64();
     } else {
-        return (1.0 - delta) * sorted_data[lhs as usize].f64() +
-               delta * sorted_data[lhs as usize + 1].f64();
+        return (1.0 - delta) * sorted_data.at(lhs as usize).f64() +
+               delta * sorted_data.at(lhs as usize + 1).f64();
     }
 }
 
+// sorted_f64 returns a sorted copy of the data for the functions that need
+// sorted input
+pub(crate) fn sorted_f64<T: F64, D: Data<T> + ?Sized>(data: &D) -> Vec<f64> {
+    let mut sorted: Vec<f64> = data.iter().map(|x| x.f64()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+    sorted
+}
+
 // skew
 
-pub fn skew<T: F64>(data: &[T]) -> f64 {
+pub fn skew<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mean = mean(data);
     let sd = sd_mean(data, mean);
     skew_mean_sd(data, mean, sd)
 }
 
 // Skew_mean_sd calculates the skewness of a dataset
-pub fn skew_mean_sd<T: F64>(data: &[T], mean: f64, sd: f64) -> f64 {
+pub fn skew_mean_sd<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64, sd: f64) -> f64 {
     let mut skew = 0.0;
     for (i, val) in data.iter().enumerate() {
         let x = (val.f64() - mean) / sd;
@@ -319,7 +412,11 @@ pub fn skew_mean_sd<T: F64>(data: &[T], mean: f64, sd: f64) -> f64 {
 // runs a t-test between two datasets representing independent
 // samples. Tests to see if the difference between means of the
 // samples is different from zero.
-pub fn t_test<T: F64>(data1: &[T], data2: &[T]) -> f64 {
+pub fn t_test<T, D1, D2>(data1: &D1, data2: &D2) -> f64
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
     let n1 = data1.len() as f64;
     let n2 = data2.len() as f64;
 
@@ -333,81 +430,186 @@ pub fn t_test<T: F64>(data1: &[T], data2: &[T]) -> f64 {
 
 // variance
 
-fn _variance<T: F64>(data: &[T], mean: f64) -> f64 {
+fn _variance<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     let mut variance = 0.0;
 
     // calculate the sum of the squares
     for (i, val) in data.iter().enumerate() {
         let delta = val.f64() - mean;
-        // TODO: long double for variance... How to implement in Rust?
+        // variance_with and tss_with accumulate with compensated or exact sums
         variance += ((delta * delta) - variance) / (i + 1) as f64;
     }
     variance
 }
 
-pub fn variance_with_fixed_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+pub fn variance_with_fixed_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     _variance(data, mean)
 }
 
-pub fn sd_with_fixed_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+pub fn sd_with_fixed_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     _variance(data, mean).sqrt()
 }
 
-pub fn variance_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+pub fn variance_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     let variance = _variance(data, mean);
     variance * (data.len() as f64) / (data.len() - 1) as f64
 }
 
-pub fn sd_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+pub fn sd_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     let variance = _variance(data, mean);
     (variance * (data.len() as f64) / (data.len() - 1) as f64).sqrt()
 }
 
-pub fn variance<T: F64>(data: &[T]) -> f64 {
+pub fn variance<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mean = mean(data);
     variance_mean(data, mean)
 }
 
-pub fn sd<T: F64>(data: &[T]) -> f64 {
+pub fn sd<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mean = mean(data);
     return sd_mean(data, mean);
 }
 
-// tss_mean takes a dataset and finds the sum of squares about the mean
-pub fn tss_mean<T: F64>(data: &[T], mean: f64) -> f64 {
+// variance_mean_with is variance_mean with the given accuracy
+pub fn variance_mean_with<T, D>(data: &D, mean: f64, accuracy: Accuracy) -> f64
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => variance_mean(data, mean),
+        Accuracy::Compensated | Accuracy::Exact => {
+            tss_mean_with(data, mean, accuracy) / (data.len() - 1) as f64
+        }
+    }
+}
+
+pub fn sd_mean_with<T, D>(data: &D, mean: f64, accuracy: Accuracy) -> f64
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    variance_mean_with(data, mean, accuracy).sqrt()
+}
+
+// variance_with is variance with the given accuracy
+pub fn variance_with<T: F64, D: Data<T> + ?Sized>(data: &D, accuracy: Accuracy) -> f64 {
+    match accuracy {
+        Accuracy::Fast => variance(data),
+        Accuracy::Compensated | Accuracy::Exact => {
+            tss_with(data, accuracy) / (data.len() - 1) as f64
+        }
+    }
+}
+
+pub fn sd_with<T: F64, D: Data<T> + ?Sized>(data: &D, accuracy: Accuracy) -> f64 {
+    variance_with(data, accuracy).sqrt()
+}
+
+// tss_mean takes a dataset and finds the sum of squares about the mean, see
+// tss_mean_with for more accurate sums
+pub fn tss_mean<T: F64, D: Data<T> + ?Sized>(data: &D, mean: f64) -> f64 {
     let mut res = 0.0;
 
     // find the sum of the squares
-    for val in data {
+    for val in data.iter() {
         let delta = val.f64() - mean;
         res += delta * delta;
     }
     res
 }
 
-pub fn tss<T: F64>(data: &[T]) -> f64 {
+pub fn tss<T: F64, D: Data<T> + ?Sized>(data: &D) -> f64 {
     let mean = mean(data);
     return tss_mean(data, mean);
 }
 
+// add_weighted_square adds w (d + e)^2 to sum exactly
+fn add_weighted_square(sum: &mut ExactSum, w: f64, d: f64, e: f64) {
+    for &(a, b) in &[(d, d), (2.0 * d, e), (e, e)] {
+        let (p, q) = accuracy::two_product(a, b);
+        sum.add_product(w, p);
+        sum.add_product(w, q);
+    }
+}
+
+// tss_sums returns the sums of the squares and of the deviations about mean,
+// compensated or exact as set by accuracy
+fn tss_sums<T, D>(data: &D, mean: f64, accuracy: Accuracy) -> (f64, f64)
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    if accuracy == Accuracy::Exact {
+        let mut tss = ExactSum::new();
+        let mut dev = ExactSum::new();
+        for val in data.iter() {
+            // the deviation d + e without rounding
+            let (d, e) = two_sum(val.f64(), -mean);
+            add_weighted_square(&mut tss, 1.0, d, e);
+            dev.add(d);
+            dev.add(e);
+        }
+        return (tss.sum(), dev.sum());
+    }
+
+    let mut tss = NeumaierSum::new();
+    let mut dev = NeumaierSum::new();
+    for val in data.iter() {
+        let delta = val.f64() - mean;
+        tss.add(delta * delta);
+        dev.add(delta);
+    }
+    (tss.sum(), dev.sum())
+}
+
+// tss_mean_with is tss_mean with the given accuracy
+pub fn tss_mean_with<T, D>(data: &D, mean: f64, accuracy: Accuracy) -> f64
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => tss_mean(data, mean),
+        Accuracy::Compensated | Accuracy::Exact => tss_sums(data, mean, accuracy).0,
+    }
+}
+
+// tss_with is tss with the given accuracy. The compensated and exact sums of
+// squares are corrected for the rounding error of the mean, which leaves the
+// deviations summing to slightly off zero.
+pub fn tss_with<T: F64, D: Data<T> + ?Sized>(data: &D, accuracy: Accuracy) -> f64 {
+    match accuracy {
+        Accuracy::Fast => tss(data),
+        Accuracy::Compensated | Accuracy::Exact => {
+            let (tss, dev) = tss_sums(data, mean_with(data, accuracy), accuracy);
+            tss - dev * dev / data.len() as f64
+        }
+    }
+}
+
 // wabsdev
 
-pub fn w_absdev<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_absdev<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wmean = w_mean(w, data);
     w_absdev_mean(w, data, wmean)
 }
 
 // WAbsdev_mean calculates the weighted absolute deviation of a dataset
-pub fn w_absdev_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+pub fn w_absdev_mean<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let mut wabsdev = 0.0;
     let mut weight = 0.0;
 
     // calculate the sum of the absolute deviations
     for i in 0..data.len() {
-        let wi = w[i].f64();
+        let wi = w.at(i).f64();
 
         if wi > 0.0 {
-            let delta = (data[i].f64() - wmean).abs();
+            let delta = (data.at(i).f64() - wmean).abs();
             weight += wi;
             wabsdev += (delta - wabsdev) * (wi / weight);
         }
@@ -417,19 +619,27 @@ pub fn w_absdev_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
 
 // wkurtosis
 
-pub fn w_kurtosis<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_kurtosis<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wmean = w_mean(w, data);
     let wsd = w_sd_mean(w, data, wmean);
     w_kurtosis_mean_sd(w, data, wmean, wsd)
 }
 
 // w_kurtosis_mean calculates the kurtosis of a dataset
-pub fn w_kurtosis_mean_sd<T: F64>(w: &[T], data: &[T], wmean: f64, wsd: f64) -> f64 {
+pub fn w_kurtosis_mean_sd<T, W, D>(w: &W, data: &D, wmean: f64, wsd: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let mut wavg = 0.0;
     let mut weight = 0.0;
 
     for (i, val) in data.iter().enumerate() {
-        let wi = w[i].f64();
+        let wi = w.at(i).f64();
 
         if wi > 0.0 {
             let x = (val.f64() - wmean) / wsd;
@@ -443,12 +653,16 @@ pub fn w_kurtosis_mean_sd<T: F64>(w: &[T], data: &[T], wmean: f64, wsd: f64) ->
 // wmean
 
 // w_mean calculates the weighted arithmetic mean of a dataset
-pub fn w_mean<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_mean<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let mut wmean = 0.0;
     let mut weight = 0.0;
 
     for (i, val) in data.iter().enumerate() {
-        let wi = w[i].f64();
+        let wi = w.at(i).f64();
 
         if wi > 0.0 {
             weight += wi;
@@ -458,21 +672,64 @@ pub fn w_mean<T: F64>(w: &[T], data: &[T]) -> f64 {
     wmean
 }
 
+// w_mean_with is w_mean with the given accuracy
+pub fn w_mean_with<T, W, D>(w: &W, data: &D, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => w_mean(w, data),
+        Accuracy::Compensated => {
+            let mut sum = NeumaierSum::new();
+            let mut weight = NeumaierSum::new();
+            for (i, val) in data.iter().enumerate() {
+                let wi = w.at(i).f64();
+                if wi > 0.0 {
+                    sum.add(wi * val.f64());
+                    weight.add(wi);
+                }
+            }
+            sum.sum() / weight.sum()
+        }
+        Accuracy::Exact => {
+            let mut sum = ExactSum::new();
+            let mut weight = ExactSum::new();
+            for (i, val) in data.iter().enumerate() {
+                let wi = w.at(i).f64();
+                if wi > 0.0 {
+                    sum.add_product(wi, val.f64());
+                    weight.add(wi);
+                }
+            }
+            sum.div(&weight)
+        }
+    }
+}
+
 // wskew
 
-pub fn w_skew<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_skew<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wmean = w_mean(w, data);
     let wsd = w_sd_mean(w, data, wmean);
     return w_skew_mean_sd(w, data, wmean, wsd);
 }
 
 // Compute the weighted skewness of a dataset
-pub fn w_skew_mean_sd<T: F64>(w: &[T], data: &[T], wmean: f64, wsd: f64) -> f64 {
+pub fn w_skew_mean_sd<T, W, D>(w: &W, data: &D, wmean: f64, wsd: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let mut wskew = 0.0;
     let mut weight = 0.0;
 
     for (i, val) in data.iter().enumerate() {
-        let wi = w[i].f64();
+        let wi = w.at(i).f64();
 
         if wi > 0.0 {
             let x = (val.f64() - wmean) / wsd;
@@ -485,7 +742,11 @@ pub fn w_skew_mean_sd<T: F64>(w: &[T], data: &[T], wmean: f64, wsd: f64) -> f64
 
 // wvariance
 
-fn wvariance<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+fn wvariance<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let mut weight = 0.0;
     let mut wvariance = 0.0;
 
@@ -493,7 +754,7 @@ fn wvariance<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
     for (i, val) in w.iter().enumerate() {
         let wi = val.f64();
         if wi > 0.0 {
-            let delta = data[i].f64() - wmean;
+            let delta = data.at(i).f64() - wmean;
             weight += wi;
             wvariance += (delta * delta - wvariance) * (wi / weight);
         }
@@ -501,12 +762,12 @@ fn wvariance<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
     wvariance
 }
 
-fn factor<T: F64>(w: &[T]) -> f64 {
+fn factor<T: F64, W: Data<T> + ?Sized>(w: &W) -> f64 {
     let mut a = 0.0;
     let mut b = 0.0;
 
     // the sum of the squares
-    for val in w {
+    for val in w.iter() {
         let wi = val.f64();
         if wi > 0.0 {
             a += wi;
@@ -516,45 +777,159 @@ fn factor<T: F64>(w: &[T]) -> f64 {
     (a * a) / ((a * a) - b)
 }
 
-pub fn w_variance_with_fixed_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+// w_variance_scale returns Sum w / ((Sum w)^2 - Sum w^2), which scales the
+// weighted sum of squares to the weighted variance like factor, for the
+// accuracy Compensated or Exact. The difference cancels when one weight
+// dominates, so the squares are formed without rounding and the difference
+// is accumulated like the sums. The exact scale is correctly rounded.
+fn w_variance_scale<T: F64, W: Data<T> + ?Sized>(w: &W, accuracy: Accuracy) -> f64 {
+    if accuracy == Accuracy::Exact {
+        let mut a = ExactSum::new();
+        let mut d = ExactSum::new();
+        for val in w.iter() {
+            let wi = val.f64();
+            if wi > 0.0 {
+                a.add(wi);
+                d.add_product(-wi, wi);
+            }
+        }
+        d.add_square(&a);
+        return a.div(&d);
+    }
+
+    let mut a = NeumaierSum::new();
+    let mut d = NeumaierSum::new();
+    for val in w.iter() {
+        let wi = val.f64();
+        if wi > 0.0 {
+            a.add(wi);
+            let (p, e) = two_product(wi, wi);
+            d.add(-p);
+            d.add(-e);
+        }
+    }
+    // (hi + lo)^2 with the square of lo too small to matter
+    let (hi, lo) = a.parts();
+    let (p, e) = two_product(hi, hi);
+    d.add(p);
+    d.add(e);
+    d.add(2.0 * hi * lo);
+    (hi + lo) / d.sum()
+}
+
+pub fn w_variance_with_fixed_mean<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     wvariance(w, data, wmean)
 }
 
-pub fn wsd_with_fixed_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+pub fn wsd_with_fixed_mean<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wvariance = wvariance(w, data, wmean);
     wvariance.sqrt()
 }
 
-pub fn w_variance_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+pub fn w_variance_mean<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let variance = wvariance(w, data, wmean);
     let scale = factor(w);
 
     scale * variance
 }
 
-pub fn w_sd_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+pub fn w_sd_mean<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let variance = wvariance(w, data, wmean);
     let scale = factor(w);
     (scale * variance).sqrt()
 }
 
-pub fn w_sd<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_sd<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wmean = w_mean(w, data);
     w_sd_mean(w, data, wmean)
 }
 
-pub fn w_variance<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_variance<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wmean = w_mean(w, data);
     w_variance_mean(w, data, wmean)
 }
 
-// w_tss_mean takes a dataset and finds the weighted sum of squares about wmean
-pub fn w_tss_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
+// w_variance_mean_with is w_variance_mean with the given accuracy
+pub fn w_variance_mean_with<T, W, D>(w: &W, data: &D, wmean: f64, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => w_variance_mean(w, data, wmean),
+        Accuracy::Compensated | Accuracy::Exact => {
+            let (tss, _, _) = w_tss_sums(w, data, wmean, accuracy);
+            tss * w_variance_scale(w, accuracy)
+        }
+    }
+}
+
+pub fn w_sd_mean_with<T, W, D>(w: &W, data: &D, wmean: f64, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    w_variance_mean_with(w, data, wmean, accuracy).sqrt()
+}
+
+// w_variance_with is w_variance with the given accuracy
+pub fn w_variance_with<T, W, D>(w: &W, data: &D, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => w_variance(w, data),
+        Accuracy::Compensated | Accuracy::Exact => {
+            w_corrected_tss(w, data, accuracy) * w_variance_scale(w, accuracy)
+        }
+    }
+}
+
+pub fn w_sd_with<T, W, D>(w: &W, data: &D, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    w_variance_with(w, data, accuracy).sqrt()
+}
+
+// w_tss_mean takes a dataset and finds the weighted sum of squares about wmean,
+// see w_tss_mean_with for more accurate sums
+pub fn w_tss_mean<T, W, D>(w: &W, data: &D, wmean: f64) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let mut res = 0.0;
 
     // find the sum of the squares
     for (i, val) in data.iter().enumerate() {
-        let wi = w[i].f64();
+        let wi = w.at(i).f64();
         if wi > 0.0 {
             let delta = val.f64() - wmean;
             res += wi * delta * delta;
@@ -563,7 +938,87 @@ pub fn w_tss_mean<T: F64>(w: &[T], data: &[T], wmean: f64) -> f64 {
     res
 }
 
-pub fn w_tss<T: F64>(w: &[T], data: &[T]) -> f64 {
+pub fn w_tss<T, W, D>(w: &W, data: &D) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
     let wmean = w_mean(w, data);
     return w_tss_mean(w, data, wmean);
 }
+
+// w_tss_sums returns the weighted sums of the squares and of the deviations
+// about wmean, and the sum of the weights, compensated or exact as set by
+// accuracy
+fn w_tss_sums<T, W, D>(w: &W, data: &D, wmean: f64, accuracy: Accuracy) -> (f64, f64, f64)
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    if accuracy == Accuracy::Exact {
+        let mut tss = ExactSum::new();
+        let mut dev = ExactSum::new();
+        let mut weight = ExactSum::new();
+        for (i, val) in data.iter().enumerate() {
+            let wi = w.at(i).f64();
+            if wi > 0.0 {
+                let (d, e) = two_sum(val.f64(), -wmean);
+                add_weighted_square(&mut tss, wi, d, e);
+                dev.add_product(wi, d);
+                dev.add_product(wi, e);
+                weight.add(wi);
+            }
+        }
+        return (tss.sum(), dev.sum(), weight.sum());
+    }
+
+    let mut tss = NeumaierSum::new();
+    let mut dev = NeumaierSum::new();
+    let mut weight = NeumaierSum::new();
+    for (i, val) in data.iter().enumerate() {
+        let wi = w.at(i).f64();
+        if wi > 0.0 {
+            let delta = val.f64() - wmean;
+            tss.add(wi * delta * delta);
+            dev.add(wi * delta);
+            weight.add(wi);
+        }
+    }
+    (tss.sum(), dev.sum(), weight.sum())
+}
+
+// w_corrected_tss returns the weighted sum of squares about the weighted mean,
+// corrected for the rounding error of the mean like tss_with
+fn w_corrected_tss<T, W, D>(w: &W, data: &D, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    let wmean = w_mean_with(w, data, accuracy);
+    let (tss, dev, weight) = w_tss_sums(w, data, wmean, accuracy);
+    tss - dev * dev / weight
+}
+
+// w_tss_mean_with is w_tss_mean with the given accuracy
+pub fn w_tss_mean_with<T, W, D>(w: &W, data: &D, wmean: f64, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => w_tss_mean(w, data, wmean),
+        Accuracy::Compensated | Accuracy::Exact => w_tss_sums(w, data, wmean, accuracy).0,
+    }
+}
+
+// w_tss_with is w_tss with the given accuracy
+pub fn w_tss_with<T, W, D>(w: &W, data: &D, accuracy: Accuracy) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    match accuracy {
+        Accuracy::Fast => w_tss(w, data),
+        Accuracy::Compensated | Accuracy::Exact => w_corrected_tss(w, data, accuracy),
+    }
+}
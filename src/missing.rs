@@ -0,0 +1,355 @@
+// missing.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+
+// Statistics of data with missing values, given either as options or as
+// values with a validity mask like the bitmaps of Apache Arrow:
+//
+//     let m = missing::mean(&readings);             // &[Option<f64>]
+//     let m = missing::mean((&values, &valid));     // (&[f64], &[bool])
+//
+// The missing values are skipped. The functions of two paired datasets, like
+// covariance and correlation, use the pairwise complete observations, the
+// positions where both values are present. The weighted functions take
+// complete weights and skip the weights of the missing values, and the
+// two-sample tests p_variance and t_test skip the missing values of each
+// dataset separately. When no values remain the results are NaN, and max and
+// min return NaN at position 0, like the functions of nan.
+
+use data::Data;
+use nan::{nonempty, nonempty_pairs};
+use types::F64;
+
+// MissingData is a sequence of values of type T some of which are missing
+pub trait MissingData<T> {
+    // len returns the number of positions, missing or not
+    fn len(&self) -> usize;
+
+    // get returns the value at position i, or None if it is missing
+    fn get(&self, i: usize) -> Option<&T>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> MissingData<T> for &[Option<T>] {
+    fn len(&self) -> usize {
+        <[Option<T>]>::len(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        self[i].as_ref()
+    }
+}
+
+impl<T, const N: usize> MissingData<T> for &[Option<T>; N] {
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        self[i].as_ref()
+    }
+}
+
+impl<T> MissingData<T> for &Vec<Option<T>> {
+    fn len(&self) -> usize {
+        <[Option<T>]>::len(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        self[i].as_ref()
+    }
+}
+
+// (values, valid) holds value i if valid[i] is true
+impl<'a, T, D, V> MissingData<T> for (&'a D, &'a V)
+    where D: Data<T> + ?Sized,
+          V: Data<bool> + ?Sized
+{
+    fn len(&self) -> usize {
+        assert!(self.0.len() == self.1.len(), "values and mask must have the same length");
+        self.0.len()
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        if *self.1.at(i) { Some(self.0.at(i)) } else { None }
+    }
+}
+
+// values returns the values present in data as f64
+pub fn values<T: F64, M: MissingData<T>>(data: M) -> Vec<f64> {
+    (0..data.len()).filter_map(|i| data.get(i).map(|v| v.f64())).collect()
+}
+
+// pairs returns the pairs of values of data1 and data2 at the positions where
+// both are present
+pub fn pairs<T, M1, M2>(data1: M1, data2: M2) -> (Vec<f64>, Vec<f64>)
+    where T: F64,
+          M1: MissingData<T>,
+          M2: MissingData<T>
+{
+    assert!(data1.len() == data2.len(), "datasets must have the same length");
+
+    let mut values1 = Vec::new();
+    let mut values2 = Vec::new();
+    for i in 0..data1.len() {
+        if let (Some(x), Some(y)) = (data1.get(i), data2.get(i)) {
+            values1.push(x.f64());
+            values2.push(y.f64());
+        }
+    }
+    (values1, values2)
+}
+
+// moments
+
+pub fn mean<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::mean)
+}
+
+pub fn variance<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::variance)
+}
+
+pub fn sd<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::sd)
+}
+
+pub fn tss<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::tss)
+}
+
+pub fn absdev<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::absdev)
+}
+
+pub fn skew<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::skew)
+}
+
+pub fn kurtosis<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::kurtosis)
+}
+
+// lag1autocorrelation finds the lag-1 autocorrelation of the values present,
+// the values on both sides of a missing one being neighbours
+pub fn lag1autocorrelation<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    nonempty(&values(data), ::lag1autocorrelation)
+}
+
+// minmax
+
+// max finds the first largest value present and its position, NaN and
+// position 0 if all values are missing. Like max, the first NaN is returned.
+pub fn max<T: F64, M: MissingData<T>>(data: M) -> (f64, usize) {
+    let mut max: Option<(f64, usize)> = None;
+    for i in 0..data.len() {
+        if let Some(val) = data.get(i) {
+            let x = val.f64();
+            if x.is_nan() {
+                return (x, i);
+            }
+            match max {
+                Some((m, _)) if x <= m => {}
+                _ => max = Some((x, i)),
+            }
+        }
+    }
+    max.unwrap_or((f64::NAN, 0))
+}
+
+// min finds the first smallest value present and its position, NaN and
+// position 0 if all values are missing. Like min, the first NaN is returned.
+pub fn min<T: F64, M: MissingData<T>>(data: M) -> (f64, usize) {
+    let mut min: Option<(f64, usize)> = None;
+    for i in 0..data.len() {
+        if let Some(val) = data.get(i) {
+            let x = val.f64();
+            if x.is_nan() {
+                return (x, i);
+            }
+            match min {
+                Some((m, _)) if x >= m => {}
+                _ => min = Some((x, i)),
+            }
+        }
+    }
+    min.unwrap_or((f64::NAN, 0))
+}
+
+// quantiles
+
+// quantile returns the quantile f of the values present, sorting a copy like
+// quantile_from_sorted_data. It is NaN if all values are missing or if any
+// value present is NaN.
+pub fn quantile<T: F64, M: MissingData<T>>(data: M, f: f64) -> f64 {
+    let mut x = values(data);
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return f64::NAN;
+    }
+    x.sort_by(|a, b| a.total_cmp(b));
+    ::quantile_from_sorted_data(&x, f)
+}
+
+// median returns the median of the values present like quantile with
+// f = 0.5
+pub fn median<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    let mut x = values(data);
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return f64::NAN;
+    }
+    x.sort_by(|a, b| a.total_cmp(b));
+    ::median_from_sorted_data(&x)
+}
+
+// mad returns the scaled median absolute deviation of the values present
+// like mad. It is NaN if all values are missing or if any value present is
+// NaN.
+pub fn mad<T: F64, M: MissingData<T>>(data: M) -> f64 {
+    let x = values(data);
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return f64::NAN;
+    }
+    ::mad(&x)
+}
+
+// pairs
+
+pub fn covariance<T, M1, M2>(data1: M1, data2: M2) -> f64
+    where T: F64,
+          M1: MissingData<T>,
+          M2: MissingData<T>
+{
+    let (x, y) = pairs(data1, data2);
+    nonempty_pairs(&x, &y, ::covariance)
+}
+
+pub fn correlation<T, M1, M2>(data1: M1, data2: M2) -> f64
+    where T: F64,
+          M1: MissingData<T>,
+          M2: MissingData<T>
+{
+    let (x, y) = pairs(data1, data2);
+    nonempty_pairs(&x, &y, ::correlation)
+}
+
+// two samples
+
+pub fn p_variance<T, M1, M2>(data1: M1, data2: M2) -> f64
+    where T: F64,
+          M1: MissingData<T>,
+          M2: MissingData<T>
+{
+    nonempty_pairs(&values(data1), &values(data2), ::p_variance)
+}
+
+pub fn t_test<T, M1, M2>(data1: M1, data2: M2) -> f64
+    where T: F64,
+          M1: MissingData<T>,
+          M2: MissingData<T>
+{
+    nonempty_pairs(&values(data1), &values(data2), ::t_test)
+}
+
+// weighted
+
+// weighted returns the weights and values at the positions where the value
+// is present
+fn weighted<T, W, M>(w: &W, data: M) -> (Vec<f64>, Vec<f64>)
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    assert!(w.len() == data.len(), "weights and data must have the same length");
+
+    let mut weights = Vec::new();
+    let mut values = Vec::new();
+    for i in 0..data.len() {
+        if let Some(x) = data.get(i) {
+            weights.push(w.at(i).f64());
+            values.push(x.f64());
+        }
+    }
+    (weights, values)
+}
+
+pub fn w_mean<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_mean)
+}
+
+pub fn w_variance<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_variance)
+}
+
+pub fn w_sd<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_sd)
+}
+
+pub fn w_tss<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_tss)
+}
+
+pub fn w_absdev<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_absdev)
+}
+
+pub fn w_skew<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_skew)
+}
+
+pub fn w_kurtosis<T, W, M>(w: &W, data: M) -> f64
+    where T: F64,
+          W: Data<T> + ?Sized,
+          M: MissingData<T>
+{
+    let (w, x) = weighted(w, data);
+    nonempty_pairs(&w, &x, ::w_kurtosis)
+}
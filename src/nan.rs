@@ -0,0 +1,413 @@
+// nan.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+
+// Statistics of data with missing values marked by NaN, like nanmean,
+// nanvar and nanmedian of NumPy.
+//
+// The functions at the crate root let a NaN spoil the result, or in the case
+// of the quantiles give a meaningless one. The functions here take a
+// NanPolicy that sets what to do with NaN:
+//
+//     let m = nan::mean(&telemetry, NanPolicy::Omit)?;
+//     let q = nan::quantile(&telemetry, 0.95, NanPolicy::Error)?;
+//
+// Any other statistic honours a policy through apply:
+//
+//     let r = nan::apply(&telemetry, NanPolicy::Omit, lag1autocorrelation)?;
+//
+// The functions of pairs of datasets, and of weights and data, omit the pair
+// of values if either one is NaN. The functions of two independent samples
+// omit the NaN values of each sample. All of them are NaN when no values
+// remain, where the functions at the crate root would return zero or panic.
+
+use std::cmp::Ordering;
+use std::error;
+use std::fmt;
+
+use data::Data;
+use types::F64;
+
+// NanPolicy sets how the functions treat NaN values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NanPolicy {
+    // compute with the NaN values, making the result NaN
+    Propagate,
+    // leave the NaN values out, as if they weren't in the data
+    Omit,
+    // return a NanError for the first NaN value
+    Error,
+}
+
+// NanError reports the position of a NaN value under NanPolicy::Error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NanError {
+    pub index: usize,
+}
+
+impl fmt::Display for NanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "data contains NaN at position {}", self.index)
+    }
+}
+
+impl error::Error for NanError {}
+
+// values returns the values of data as f64 as set by policy
+pub fn values<T, D>(data: &D, policy: NanPolicy) -> Result<Vec<f64>, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    let mut values = Vec::with_capacity(data.len());
+    for (i, val) in data.iter().enumerate() {
+        let x = val.f64();
+        if x.is_nan() {
+            match policy {
+                NanPolicy::Propagate => {}
+                NanPolicy::Omit => continue,
+                NanPolicy::Error => return Err(NanError { index: i }),
+            }
+        }
+        values.push(x);
+    }
+    Ok(values)
+}
+
+// pairs returns the pairs of values of data1 and data2 as f64 as set by
+// policy, where a pair is NaN if either value is
+pub fn pairs<T, D1, D2>(data1: &D1,
+                        data2: &D2,
+                        policy: NanPolicy)
+                        -> Result<(Vec<f64>, Vec<f64>), NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
+    assert!(data1.len() == data2.len(), "datasets must have the same length");
+
+    let mut values1 = Vec::with_capacity(data1.len());
+    let mut values2 = Vec::with_capacity(data2.len());
+    for (i, (val1, val2)) in data1.iter().zip(data2.iter()).enumerate() {
+        let (x, y) = (val1.f64(), val2.f64());
+        if x.is_nan() || y.is_nan() {
+            match policy {
+                NanPolicy::Propagate => {}
+                NanPolicy::Omit => continue,
+                NanPolicy::Error => return Err(NanError { index: i }),
+            }
+        }
+        values1.push(x);
+        values2.push(y);
+    }
+    Ok((values1, values2))
+}
+
+// apply computes the statistic f of the values of data as set by policy
+pub fn apply<T, D, F>(data: &D, policy: NanPolicy, f: F) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized,
+          F: FnOnce(&[f64]) -> f64
+{
+    values(data, policy).map(|x| f(&x))
+}
+
+// apply_pairs computes the statistic f of the pairs of values of data1 and
+// data2 as set by policy
+pub fn apply_pairs<T, D1, D2, F>(data1: &D1,
+                                 data2: &D2,
+                                 policy: NanPolicy,
+                                 f: F)
+                                 -> Result<f64, NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized,
+          F: FnOnce(&[f64], &[f64]) -> f64
+{
+    pairs(data1, data2, policy).map(|(x, y)| f(&x, &y))
+}
+
+// nonempty computes the statistic f of x, NaN if x is empty
+pub(crate) fn nonempty<F: FnOnce(&[f64]) -> f64>(x: &[f64], f: F) -> f64 {
+    if x.is_empty() { f64::NAN } else { f(x) }
+}
+
+// nonempty_pairs computes the statistic f of x and y, NaN if either is empty
+pub(crate) fn nonempty_pairs<F: FnOnce(&[f64], &[f64]) -> f64>(x: &[f64], y: &[f64], f: F) -> f64 {
+    if x.is_empty() || y.is_empty() { f64::NAN } else { f(x, y) }
+}
+
+// moments
+
+pub fn mean<T: F64, D: Data<T> + ?Sized>(data: &D, policy: NanPolicy) -> Result<f64, NanError> {
+    apply(data, policy, |x| nonempty(x, ::mean))
+}
+
+pub fn variance<T, D>(data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    apply(data, policy, |x| nonempty(x, ::variance))
+}
+
+pub fn sd<T: F64, D: Data<T> + ?Sized>(data: &D, policy: NanPolicy) -> Result<f64, NanError> {
+    apply(data, policy, |x| nonempty(x, ::sd))
+}
+
+pub fn tss<T: F64, D: Data<T> + ?Sized>(data: &D, policy: NanPolicy) -> Result<f64, NanError> {
+    apply(data, policy, |x| nonempty(x, ::tss))
+}
+
+pub fn absdev<T, D>(data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    apply(data, policy, |x| nonempty(x, ::absdev))
+}
+
+pub fn skew<T: F64, D: Data<T> + ?Sized>(data: &D, policy: NanPolicy) -> Result<f64, NanError> {
+    apply(data, policy, |x| nonempty(x, ::skew))
+}
+
+pub fn kurtosis<T, D>(data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    apply(data, policy, |x| nonempty(x, ::kurtosis))
+}
+
+// lag1autocorrelation finds the lag-1 autocorrelation of the values. Omit
+// makes the values on both sides of a NaN neighbours.
+pub fn lag1autocorrelation<T, D>(data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    apply(data, policy, |x| nonempty(x, ::lag1autocorrelation))
+}
+
+// minmax
+
+// max finds the first largest value and its position in data, NaN and
+// position 0 if there are no values. Under Propagate the first NaN is
+// returned like max.
+pub fn max<T, D>(data: &D, policy: NanPolicy) -> Result<(f64, usize), NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    extreme(data, policy, Ordering::Greater)
+}
+
+// min finds the first smallest value and its position in data, NaN and
+// position 0 if there are no values. Under Propagate the first NaN is
+// returned like min.
+pub fn min<T, D>(data: &D, policy: NanPolicy) -> Result<(f64, usize), NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    extreme(data, policy, Ordering::Less)
+}
+
+fn extreme<T, D>(data: &D, policy: NanPolicy, order: Ordering) -> Result<(f64, usize), NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    let mut best: Option<(f64, usize)> = None;
+    for (i, val) in data.iter().enumerate() {
+        let x = val.f64();
+        if x.is_nan() {
+            match policy {
+                NanPolicy::Propagate => return Ok((x, i)),
+                NanPolicy::Omit => continue,
+                NanPolicy::Error => return Err(NanError { index: i }),
+            }
+        }
+        match best {
+            Some((b, _)) if x.partial_cmp(&b) != Some(order) => {}
+            _ => best = Some((x, i)),
+        }
+    }
+    Ok(best.unwrap_or((f64::NAN, 0)))
+}
+
+// quantiles
+
+// quantile returns the quantile f of data, sorting a copy of the values like
+// quantile_from_sorted_data. It is NaN if there are no values, or under
+// Propagate if any value is NaN.
+pub fn quantile<T, D>(data: &D, f: f64, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    let mut x = values(data, policy)?;
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return Ok(f64::NAN);
+    }
+    x.sort_by(|a, b| a.total_cmp(b));
+    Ok(::quantile_from_sorted_data(&x, f))
+}
+
+// median returns the median of data like quantile with f = 0.5
+pub fn median<T, D>(data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    let mut x = values(data, policy)?;
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return Ok(f64::NAN);
+    }
+    x.sort_by(|a, b| a.total_cmp(b));
+    Ok(::median_from_sorted_data(&x))
+}
+
+// mad returns the scaled median absolute deviation of data like mad. It is
+// NaN if there are no values, or under Propagate if any value is NaN.
+pub fn mad<T: F64, D: Data<T> + ?Sized>(data: &D, policy: NanPolicy) -> Result<f64, NanError> {
+    let x = values(data, policy)?;
+    if x.is_empty() || x.iter().any(|v| v.is_nan()) {
+        return Ok(f64::NAN);
+    }
+    Ok(::mad(&x))
+}
+
+// pairs
+
+pub fn covariance<T, D1, D2>(data1: &D1, data2: &D2, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
+    apply_pairs(data1, data2, policy, |x, y| nonempty_pairs(x, y, ::covariance))
+}
+
+pub fn correlation<T, D1, D2>(data1: &D1, data2: &D2, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
+    apply_pairs(data1, data2, policy, |x, y| nonempty_pairs(x, y, ::correlation))
+}
+
+// two samples
+
+// apply_samples computes the statistic f of the values of the independent
+// samples data1 and data2, each as set by policy
+fn apply_samples<T, D1, D2, F>(data1: &D1,
+                               data2: &D2,
+                               policy: NanPolicy,
+                               f: F)
+                               -> Result<f64, NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized,
+          F: FnOnce(&[f64], &[f64]) -> f64
+{
+    let x = values(data1, policy)?;
+    let y = values(data2, policy)?;
+    Ok(nonempty_pairs(&x, &y, f))
+}
+
+pub fn p_variance<T, D1, D2>(data1: &D1, data2: &D2, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
+    apply_samples(data1, data2, policy, ::p_variance)
+}
+
+pub fn t_test<T, D1, D2>(data1: &D1, data2: &D2, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
+    apply_samples(data1, data2, policy, ::t_test)
+}
+
+// weighted
+
+// apply_weighted is apply_pairs for weights, which the weighted functions
+// would skip if NaN
+fn apply_weighted<T, W, D, F>(w: &W, data: &D, policy: NanPolicy, f: F) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized,
+          F: FnOnce(&[f64], &[f64]) -> f64
+{
+    apply_pairs(w, data, policy, |w, x| {
+        if w.iter().any(|v| v.is_nan()) {
+            f64::NAN
+        } else {
+            nonempty_pairs(w, x, f)
+        }
+    })
+}
+
+pub fn w_mean<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_mean)
+}
+
+pub fn w_variance<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_variance)
+}
+
+pub fn w_sd<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_sd)
+}
+
+pub fn w_tss<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_tss)
+}
+
+pub fn w_absdev<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_absdev)
+}
+
+pub fn w_skew<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_skew)
+}
+
+pub fn w_kurtosis<T, W, D>(w: &W, data: &D, policy: NanPolicy) -> Result<f64, NanError>
+    where T: F64,
+          W: Data<T> + ?Sized,
+          D: Data<T> + ?Sized
+{
+    apply_weighted(w, data, policy, ::w_kurtosis)
+}
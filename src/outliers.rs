@@ -0,0 +1,308 @@
+// outliers.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Outlier detection.
+//
+// tukey_fences, z_scores and modified_z_scores give every value a score and
+// flag the values whose score exceeds a threshold. grubbs_test,
+// grubbs_iterative, generalized_esd and dixon_q_test are significance tests
+// that assume the remaining data is Gaussian. They compare a statistic of
+// the most extreme value with its critical value at significance level
+// alpha.
+
+use specfunc::t_isf;
+use summary::five_number_summary;
+use data::Data;
+use types::F64;
+use {mad0, max, mean, median_from_sorted_data, min, sd_mean, sorted_f64};
+
+// OutlierScores holds the score of every value and the indices of the
+// values whose absolute score exceeds the threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlierScores {
+    pub scores: Vec<f64>,
+    pub threshold: f64,
+    pub indices: Vec<usize>,
+}
+
+impl OutlierScores {
+    fn new(scores: Vec<f64>, threshold: f64) -> OutlierScores {
+        let indices = scores.iter()
+            .enumerate()
+            .filter(|&(_, s)| s.abs() > threshold)
+            .map(|(i, _)| i)
+            .collect();
+
+        OutlierScores {
+            scores,
+            threshold,
+            indices,
+        }
+    }
+}
+
+// OutlierTest holds the outcome of a test of the value at index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierTest {
+    pub index: usize,
+    pub statistic: f64,
+    pub critical: f64,
+}
+
+impl OutlierTest {
+    // is_outlier returns true if the statistic exceeds its critical value
+    pub fn is_outlier(&self) -> bool {
+        self.statistic > self.critical
+    }
+}
+
+// SequentialTest holds the steps of a test that removes the most extreme
+// value after every step, and the indices of the values found to be outliers
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequentialTest {
+    pub steps: Vec<OutlierTest>,
+    pub indices: Vec<usize>,
+}
+
+// tukey
+
+// tukey_fences scores every value by its distance beyond the quartiles in
+// units of the interquartile range, zero for values between the quartiles.
+// Values outside the fences q1 - k iqr and q3 + k iqr score more than k.
+pub fn tukey_fences<T: F64, D: Data<T> + ?Sized>(data: &D, k: f64) -> OutlierScores {
+    let s = five_number_summary(data);
+    let iqr = s.iqr();
+
+    let scores = data.iter()
+        .map(|x| {
+            let x = x.f64();
+            let beyond = if x < s.q1 {
+                s.q1 - x
+            } else if x > s.q3 {
+                x - s.q3
+            } else {
+                0.0
+            };
+            if beyond > 0.0 { beyond / iqr } else { 0.0 }
+        })
+        .collect();
+    OutlierScores::new(scores, k)
+}
+
+// z-score
+
+// z_scores scores every value by (x - mean)/sd. Note that a single outlier
+// inflates sd, so in a sample of n values no score can exceed (n - 1)/sqrt(n).
+pub fn z_scores<T: F64, D: Data<T> + ?Sized>(data: &D, threshold: f64) -> OutlierScores {
+    let mean = mean(data);
+    let sd = sd_mean(data, mean);
+
+    let scores = data.iter().map(|x| (x.f64() - mean) / sd).collect();
+    OutlierScores::new(scores, threshold)
+}
+
+// modified_z_scores scores every value by 0.6745 (x - median)/mad0, which
+// outliers hardly affect. B. Iglewicz and D. C. Hoaglin, "How to Detect and
+// Handle Outliers", 1993, recommend the threshold 3.5.
+pub fn modified_z_scores<T: F64, D: Data<T> + ?Sized>(data: &D, threshold: f64) -> OutlierScores {
+    let median = median_from_sorted_data(&sorted_f64(data));
+    let mad = mad0(data);
+
+    let scores = data.iter()
+        .map(|x| {
+            let delta = x.f64() - median;
+            if delta == 0.0 { 0.0 } else { 0.6745 * delta / mad }
+        })
+        .collect();
+    OutlierScores::new(scores, threshold)
+}
+
+// grubbs
+
+// grubbs_critical returns the two-sided critical value of the Grubbs
+// statistic for n values at significance level alpha
+fn grubbs_critical(n: usize, alpha: f64) -> f64 {
+    let n = n as f64;
+    let t = t_isf(alpha / (2.0 * n), n - 2.0);
+    (n - 1.0) / n.sqrt() * (t * t / (n - 2.0 + t * t)).sqrt()
+}
+
+// grubbs_statistic returns the position of the value farthest from the mean
+// and max |x - mean|/sd
+fn grubbs_statistic(data: &[f64]) -> (usize, f64) {
+    let mean = mean(data);
+    let sd = sd_mean(data, mean);
+
+    let mut index = 0;
+    let mut largest = 0.0;
+    for (i, &x) in data.iter().enumerate() {
+        let delta = (x - mean).abs();
+        if delta > largest {
+            largest = delta;
+            index = i;
+        }
+    }
+    (index, if largest > 0.0 { largest / sd } else { 0.0 })
+}
+
+// grubbs_test tests whether the value farthest from the mean is an outlier,
+// see F. E. Grubbs, "Procedures for Detecting Outlying Observations in
+// Samples", Technometrics, Vol 11, No 1, 1969
+pub fn grubbs_test<T: F64, D: Data<T> + ?Sized>(data: &D, alpha: f64) -> OutlierTest {
+    assert!(data.len() > 2, "the Grubbs test needs at least three values");
+    assert!(alpha > 0.0 && alpha < 1.0, "alpha must lie in (0, 1)");
+
+    let data: Vec<f64> = data.iter().map(|x| x.f64()).collect();
+    let (index, statistic) = grubbs_statistic(&data);
+    OutlierTest {
+        index,
+        statistic,
+        critical: grubbs_critical(data.len(), alpha),
+    }
+}
+
+// remaining tracks the values left after removing outliers one at a time,
+// with their indices in the original data
+struct Remaining {
+    values: Vec<f64>,
+    indices: Vec<usize>,
+}
+
+impl Remaining {
+    fn new<T: F64, D: Data<T> + ?Sized>(data: &D) -> Remaining {
+        Remaining {
+            values: data.iter().map(|x| x.f64()).collect(),
+            indices: (0..data.len()).collect(),
+        }
+    }
+
+    // remove removes the value at position i, returning its original index
+    fn remove(&mut self, i: usize) -> usize {
+        self.values.remove(i);
+        self.indices.remove(i)
+    }
+}
+
+// grubbs_iterative repeats the Grubbs test, removing the outlier after every
+// significant step, until a step is not significant or two values remain
+pub fn grubbs_iterative<T: F64, D: Data<T> + ?Sized>(data: &D, alpha: f64) -> SequentialTest {
+    assert!(alpha > 0.0 && alpha < 1.0, "alpha must lie in (0, 1)");
+
+    let mut remaining = Remaining::new(data);
+    let mut steps = Vec::new();
+    let mut indices = Vec::new();
+
+    while remaining.values.len() > 2 {
+        let (i, statistic) = grubbs_statistic(&remaining.values);
+        let critical = grubbs_critical(remaining.values.len(), alpha);
+        let step = OutlierTest {
+            index: remaining.indices[i],
+            statistic,
+            critical,
+        };
+        steps.push(step);
+
+        if !step.is_outlier() {
+            break;
+        }
+        indices.push(remaining.remove(i));
+    }
+
+    SequentialTest { steps, indices }
+}
+
+// generalized esd
+
+// generalized_esd performs the generalized extreme Studentized deviate test
+// for up to max_outliers outliers of B. Rosner, "Percentage Points for a
+// Generalized ESD Many-Outlier Procedure", Technometrics, Vol 25, No 2, 1983.
+// All max_outliers steps are performed, and the number of outliers is the
+// last step whose statistic exceeds its critical value, which avoids the
+// masking of outliers by each other that stops grubbs_iterative.
+pub fn generalized_esd<T, D>(data: &D, max_outliers: usize, alpha: f64) -> SequentialTest
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    assert!(max_outliers + 2 <= data.len(),
+            "the generalized ESD test needs at least max_outliers + 2 values");
+    assert!(alpha > 0.0 && alpha < 1.0, "alpha must lie in (0, 1)");
+
+    let mut remaining = Remaining::new(data);
+    let mut steps = Vec::with_capacity(max_outliers);
+
+    for _ in 0..max_outliers {
+        let (i, statistic) = grubbs_statistic(&remaining.values);
+        let critical = grubbs_critical(remaining.values.len(), alpha);
+        steps.push(OutlierTest {
+            index: remaining.remove(i),
+            statistic,
+            critical,
+        });
+    }
+
+    let n_outliers = steps.iter().rposition(|s| s.is_outlier()).map_or(0, |i| i + 1);
+    let indices = steps[..n_outliers].iter().map(|s| s.index).collect();
+    SequentialTest { steps, indices }
+}
+
+// dixon
+
+// critical values of Dixon's Q = r10 at the significance levels 0.10, 0.05
+// and 0.01 for 3 to 10 values, from D. B. Rorabacher, "Statistical Treatment
+// for Rejection of Deviant Values", Anal. Chem., Vol 63, No 2, 1991
+const DIXON_ALPHA: [f64; 3] = [0.10, 0.05, 0.01];
+const DIXON_Q: [[f64; 3]; 8] = [[0.941, 0.970, 0.994],
+                                [0.765, 0.829, 0.926],
+                                [0.642, 0.710, 0.821],
+                                [0.560, 0.625, 0.740],
+                                [0.507, 0.568, 0.680],
+                                [0.468, 0.526, 0.634],
+                                [0.437, 0.493, 0.598],
+                                [0.412, 0.466, 0.568]];
+
+// dixon_q_test tests whether the smallest or the largest of 3 to 10 values,
+// whichever has the larger gap to its neighbour, is an outlier. Q is the gap
+// divided by the range. alpha must be 0.10, 0.05 or 0.01.
+pub fn dixon_q_test<T: F64, D: Data<T> + ?Sized>(data: &D, alpha: f64) -> OutlierTest {
+    let n = data.len();
+    assert!((3..=10).contains(&n), "Dixon's Q test needs 3 to 10 values");
+    let level = DIXON_ALPHA.iter()
+        .position(|&a| a == alpha)
+        .expect("alpha must be 0.10, 0.05 or 0.01");
+
+    let sorted = sorted_f64(data);
+    let range = sorted[n - 1] - sorted[0];
+    let (q_low, q_high) = if range > 0.0 {
+        ((sorted[1] - sorted[0]) / range, (sorted[n - 1] - sorted[n - 2]) / range)
+    } else {
+        (0.0, 0.0)
+    };
+
+    let (index, statistic) = if q_high >= q_low {
+        (max(data).1, q_high)
+    } else {
+        (min(data).1, q_low)
+    };
+
+    OutlierTest {
+        index,
+        statistic,
+        critical: DIXON_Q[n - 3][level],
+    }
+}
@@ -0,0 +1,291 @@
+// permutation.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Permutation tests for two independent samples.
+//
+// Unlike t_test these make no assumption about the distribution of the
+// data. Under the null hypothesis both samples come from the same
+// distribution, so every split of the pooled data into groups of the
+// original sizes is equally likely. The p-value is the fraction of the
+// splits whose statistic is at least as extreme as the observed one:
+//
+//     let mut rng = Xoshiro256StarStar::new(42);
+//     let t = permutation_test(data1, data2, |a, b| mean(a) - mean(b), 10000,
+//                              Alternative::TwoSided, &mut rng);
+//
+// When the number of distinct splits doesn't exceed n_permutations all of
+// them are enumerated and the p-value is exact. Otherwise n_permutations
+// random splits are drawn and the p-value (count + 1)/(n_permutations + 1)
+// is used, see B. Phipson and G. K. Smyth, "Permutation P-values Should
+// Never Be Zero", Stat. Appl. Genet. Mol. Biol., Vol 9, No 1, 2010.
+
+use std::thread;
+
+use data::Data;
+use rng::{Rng, Split};
+use types::F64;
+
+// Alternative is the alternative hypothesis of the test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alternative {
+    // the statistic differs from its null distribution in either direction
+    TwoSided,
+    // the statistic is smaller than under the null hypothesis
+    Less,
+    // the statistic is larger than under the null hypothesis
+    Greater,
+}
+
+// PermutationTest holds the outcome of a permutation test
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PermutationTest {
+    // the statistic of the original samples
+    pub statistic: f64,
+    pub p_value: f64,
+    // the number of splits the statistic was compared with
+    pub n_permutations: usize,
+    // true if all splits were enumerated
+    pub exact: bool,
+}
+
+// counts of the permuted statistics that are less than or equal to, and
+// greater than or equal to the observed statistic
+#[derive(Clone, Copy, Default)]
+struct Counts {
+    less: usize,
+    greater: usize,
+}
+
+impl Counts {
+    fn add(&mut self, t: f64, observed: f64) {
+        // allow for rounding differences between equivalent splits
+        let gamma = (1.0e-14 * observed).abs();
+        if t <= observed + gamma {
+            self.less += 1;
+        }
+        if t >= observed - gamma {
+            self.greater += 1;
+        }
+    }
+
+    fn p_value(&self, alternative: Alternative, total: usize, exact: bool) -> f64 {
+        let p = |count: usize| if exact {
+            count as f64 / total as f64
+        } else {
+            (count + 1) as f64 / (total + 1) as f64
+        };
+
+        match alternative {
+            Alternative::Less => p(self.less),
+            Alternative::Greater => p(self.greater),
+            Alternative::TwoSided => (2.0 * p(self.less).min(p(self.greater))).min(1.0),
+        }
+    }
+}
+
+// binomial returns n over k, saturating at usize::MAX
+fn binomial(n: usize, k: usize) -> usize {
+    let k = k.min(n - k);
+    let mut c: u128 = 1;
+    for i in 0..k {
+        c = c * (n - i) as u128 / (i + 1) as u128;
+        if c > usize::MAX as u128 {
+            return usize::MAX;
+        }
+    }
+    c as usize
+}
+
+// shuffled_split draws a random split of the pooled data by moving a random
+// selection of n1 values to the front, a partial Fisher-Yates shuffle
+fn shuffled_split<R: Rng>(pooled: &mut [f64], n1: usize, rng: &mut R) {
+    let n = pooled.len();
+    for i in 0..n1 {
+        let j = i + rng.uniform_int((n - i) as u64) as usize;
+        pooled.swap(i, j);
+    }
+}
+
+fn monte_carlo<F, R>(pooled: &[f64],
+                     n1: usize,
+                     statistic: &F,
+                     observed: f64,
+                     n_permutations: usize,
+                     rng: &mut R)
+                     -> Counts
+    where F: Fn(&[f64], &[f64]) -> f64,
+          R: Rng
+{
+    let mut pooled = pooled.to_vec();
+    let mut counts = Counts::default();
+    for _ in 0..n_permutations {
+        shuffled_split(&mut pooled, n1, rng);
+        let (a, b) = pooled.split_at(n1);
+        counts.add(statistic(a, b), observed);
+    }
+    counts
+}
+
+// exact enumerates all splits, choosing the indices of the first group in
+// lexicographic order
+fn exact<F>(pooled: &[f64], n1: usize, statistic: &F, observed: f64) -> Counts
+    where F: Fn(&[f64], &[f64]) -> f64
+{
+    let n = pooled.len();
+    let mut idx: Vec<usize> = (0..n1).collect();
+    let mut a = Vec::with_capacity(n1);
+    let mut b = Vec::with_capacity(n - n1);
+    let mut counts = Counts::default();
+
+    loop {
+        a.clear();
+        b.clear();
+        let mut k = 0;
+        for (i, &x) in pooled.iter().enumerate() {
+            if k < n1 && idx[k] == i {
+                a.push(x);
+                k += 1;
+            } else {
+                b.push(x);
+            }
+        }
+        counts.add(statistic(&a, &b), observed);
+
+        // advance to the next combination
+        let mut i = n1;
+        while i > 0 && idx[i - 1] == n - n1 + i - 1 {
+            i -= 1;
+        }
+        if i == 0 {
+            return counts;
+        }
+        idx[i - 1] += 1;
+        for j in i..n1 {
+            idx[j] = idx[j - 1] + 1;
+        }
+    }
+}
+
+fn pool<T, D1, D2>(data1: &D1, data2: &D2) -> Vec<f64>
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized
+{
+    assert!(!data1.is_empty() && !data2.is_empty(),
+            "both samples must contain at least one value");
+    data1.iter().chain(data2.iter()).map(|x| x.f64()).collect()
+}
+
+// permutation_test compares the statistic of the two samples with its
+// distribution over the splits of the pooled data
+pub fn permutation_test<T, D1, D2, F, R>(data1: &D1,
+                                         data2: &D2,
+                                         statistic: F,
+                                         n_permutations: usize,
+                                         alternative: Alternative,
+                                         rng: &mut R)
+                                         -> PermutationTest
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized,
+          F: Fn(&[f64], &[f64]) -> f64,
+          R: Rng
+{
+    let pooled = pool(data1, data2);
+    let n1 = data1.len();
+    let observed = statistic(&pooled[..n1], &pooled[n1..]);
+
+    let total = binomial(pooled.len(), n1);
+    if total <= n_permutations {
+        let counts = exact(&pooled, n1, &statistic, observed);
+        return PermutationTest {
+            statistic: observed,
+            p_value: counts.p_value(alternative, total, true),
+            n_permutations: total,
+            exact: true,
+        };
+    }
+
+    let counts = monte_carlo(&pooled, n1, &statistic, observed, n_permutations, rng);
+    PermutationTest {
+        statistic: observed,
+        p_value: counts.p_value(alternative, n_permutations, false),
+        n_permutations,
+        exact: false,
+    }
+}
+
+// permutation_test_parallel is permutation_test with the random splits
+// divided over n_threads threads. Every thread uses its own generator, split
+// off rng, so the outcome depends on the seed and on n_threads.
+// The exact test is not parallelized.
+pub fn permutation_test_parallel<T, D1, D2, F, R>(data1: &D1,
+                                                  data2: &D2,
+                                                  statistic: F,
+                                                  n_permutations: usize,
+                                                  alternative: Alternative,
+                                                  rng: &mut R,
+                                                  n_threads: usize)
+                                                  -> PermutationTest
+    where T: F64,
+          D1: Data<T> + ?Sized,
+          D2: Data<T> + ?Sized,
+          F: Fn(&[f64], &[f64]) -> f64 + Sync,
+          R: Split + Send
+{
+    assert!(n_threads > 0, "n_threads must be positive");
+
+    let pooled = pool(data1, data2);
+    let n1 = data1.len();
+    if binomial(pooled.len(), n1) <= n_permutations || n_threads == 1 {
+        return permutation_test(data1, data2, statistic, n_permutations, alternative, rng);
+    }
+
+    let observed = statistic(&pooled[..n1], &pooled[n1..]);
+
+    let counts = thread::scope(|s| {
+        let handles: Vec<_> = (0..n_threads)
+            .map(|t| {
+                let share = n_permutations / n_threads +
+                            if t < n_permutations % n_threads { 1 } else { 0 };
+                let mut thread_rng = rng.split();
+                let pooled = &pooled;
+                let statistic = &statistic;
+                s.spawn(move || {
+                    monte_carlo(pooled, n1, statistic, observed, share, &mut thread_rng)
+                })
+            })
+            .collect();
+
+        let mut counts = Counts::default();
+        for h in handles {
+            let c = h.join().unwrap();
+            counts.less += c.less;
+            counts.greater += c.greater;
+        }
+        counts
+    });
+
+    PermutationTest {
+        statistic: observed,
+        p_value: counts.p_value(alternative, n_permutations, false),
+        n_permutations,
+        exact: false,
+    }
+}
@@ -0,0 +1,460 @@
+// randist.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 James Theiler, Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Random variates from standard distributions and random sampling of
+// data, a port of gsl_ran_*.
+//
+// Every function draws its random numbers from the generator passed as the
+// first argument, so the variates can be reproduced from the seed.
+
+use std::sync::OnceLock;
+
+use rng::Rng;
+
+// gaussian
+
+// number of layers of the ziggurat, the start of the tail and the area of
+// every layer, from G. Marsaglia and W. W. Tsang, "The Ziggurat Method for
+// Generating Random Variables", Journal of Statistical Software, Vol 5,
+// No 8, 2000.
+const ZIGGURAT_N: usize = 128;
+const ZIGGURAT_R: f64 = 3.442619855899;
+const ZIGGURAT_V: f64 = 9.91256303526217e-3;
+
+struct Ziggurat {
+    // the right edges of the layers, x[0] being the virtual edge of the
+    // base layer that includes the tail
+    x: [f64; ZIGGURAT_N + 1],
+    // x[i + 1] / x[i], the part of layer i inside the density
+    ratio: [f64; ZIGGURAT_N],
+}
+
+fn ziggurat() -> &'static Ziggurat {
+    static TABLES: OnceLock<Ziggurat> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let f = |x: f64| (-0.5 * x * x).exp();
+
+        let mut x = [0.0; ZIGGURAT_N + 1];
+        x[0] = ZIGGURAT_V / f(ZIGGURAT_R);
+        x[1] = ZIGGURAT_R;
+        for i in 1..ZIGGURAT_N - 1 {
+            x[i + 1] = (-2.0 * (ZIGGURAT_V / x[i] + f(x[i])).ln()).sqrt();
+        }
+        x[ZIGGURAT_N] = 0.0;
+
+        let mut ratio = [0.0; ZIGGURAT_N];
+        for i in 0..ZIGGURAT_N {
+            ratio[i] = x[i + 1] / x[i];
+        }
+        Ziggurat { x, ratio }
+    })
+}
+
+// gaussian returns a Gaussian random variate with mean zero and standard
+// deviation sigma, using the ziggurat method in the form of J. A. Doornik,
+// "An Improved Ziggurat Method to Generate Normal Random Samples", 2005.
+pub fn gaussian<R: Rng>(rng: &mut R, sigma: f64) -> f64 {
+    let z = ziggurat();
+
+    loop {
+        // the layer from the lowest bits, the position from the upper 53
+        let bits = rng.next_u64();
+        let i = (bits & 0x7f) as usize;
+        let u = 2.0 * ((bits >> 11) as f64 * (1.0 / (1u64 << 53) as f64)) - 1.0;
+
+        if u.abs() < z.ratio[i] {
+            return sigma * u * z.x[i];
+        }
+
+        if i == 0 {
+            // sample from the tail beyond R
+            loop {
+                let x = -rng.uniform_pos().ln() / ZIGGURAT_R;
+                let y = -rng.uniform_pos().ln();
+                if 2.0 * y >= x * x {
+                    let t = ZIGGURAT_R + x;
+                    return sigma * if u > 0.0 { t } else { -t };
+                }
+            }
+        }
+
+        let x = u * z.x[i];
+        let f0 = (-0.5 * (z.x[i] * z.x[i] - x * x)).exp();
+        let f1 = (-0.5 * (z.x[i + 1] * z.x[i + 1] - x * x)).exp();
+        if f1 + rng.uniform() * (f0 - f1) < 1.0 {
+            return sigma * x;
+        }
+    }
+}
+
+// exponential
+
+// exponential returns an exponential random variate with mean mu
+pub fn exponential<R: Rng>(rng: &mut R, mu: f64) -> f64 {
+    let u = rng.uniform();
+    -mu * (-u).ln_1p()
+}
+
+// gamma
+
+// gamma returns a gamma random variate with shape a and scale b, with the
+// method of G. Marsaglia and W. W. Tsang, "A Simple Method for Generating
+// Gamma Variables", ACM Trans. Math. Software, Vol 26, No 3, 2000.
+pub fn gamma<R: Rng>(rng: &mut R, a: f64, b: f64) -> f64 {
+    assert!(a > 0.0, "the shape a must be positive");
+
+    if a < 1.0 {
+        let u = rng.uniform_pos();
+        return gamma(rng, 1.0 + a, b) * u.powf(1.0 / a);
+    }
+
+    let d = a - 1.0 / 3.0;
+    let c = (1.0 / 3.0) / d.sqrt();
+
+    loop {
+        let mut x;
+        let mut v;
+        loop {
+            x = gaussian(rng, 1.0);
+            v = 1.0 + c * x;
+            if v > 0.0 {
+                break;
+            }
+        }
+
+        v = v * v * v;
+        let u = rng.uniform_pos();
+
+        if u < 1.0 - 0.0331 * x * x * x * x {
+            return b * d * v;
+        }
+        if u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return b * d * v;
+        }
+    }
+}
+
+// beta
+
+// beta returns a beta random variate, as X / (X + Y) for the gamma variates
+// X with shape a and Y with shape b
+pub fn beta<R: Rng>(rng: &mut R, a: f64, b: f64) -> f64 {
+    let x1 = gamma(rng, a, 1.0);
+    let x2 = gamma(rng, b, 1.0);
+    x1 / (x1 + x2)
+}
+
+// chisq
+
+// chisq returns a chi-squared random variate with nu degrees of freedom
+pub fn chisq<R: Rng>(rng: &mut R, nu: f64) -> f64 {
+    2.0 * gamma(rng, nu / 2.0, 1.0)
+}
+
+// tdist
+
+// tdist returns a Student t random variate with nu degrees of freedom, as
+// Y1 / sqrt(Y2 / nu) for the unit Gaussian Y1 and chi-squared Y2
+pub fn tdist<R: Rng>(rng: &mut R, nu: f64) -> f64 {
+    let y1 = gaussian(rng, 1.0);
+    let y2 = chisq(rng, nu);
+    y1 / (y2 / nu).sqrt()
+}
+
+// poisson
+
+// poisson returns a Poisson random variate with mean mu. Large means are
+// reduced with gamma variates, see D. E. Knuth, "The Art of Computer
+// Programming", Vol 2, 3rd ed, p137.
+pub fn poisson<R: Rng>(rng: &mut R, mu: f64) -> u64 {
+    assert!(mu >= 0.0, "the mean mu must not be negative");
+
+    let mut mu = mu;
+    let mut k = 0;
+
+    while mu > 10.0 {
+        let m = (mu * (7.0 / 8.0)) as u64;
+        let x = gamma(rng, m as f64, 1.0);
+
+        if x >= mu {
+            return k + binomial(rng, mu / x, m - 1);
+        }
+        k += m;
+        mu -= x;
+    }
+
+    // this method works well when mu is small
+    let emu = (-mu).exp();
+    let mut prod = 1.0;
+    loop {
+        prod *= rng.uniform();
+        if prod <= emu {
+            return k;
+        }
+        k += 1;
+    }
+}
+
+// binomial
+
+// binomial returns the number of successes in n trials with probability p,
+// reducing large n with beta variates, see D. E. Knuth, "The Art of
+// Computer Programming", Vol 2, 3rd ed, p136.
+pub fn binomial<R: Rng>(rng: &mut R, p: f64, n: u64) -> u64 {
+    assert!((0.0..=1.0).contains(&p), "the probability p must lie in [0, 1]");
+
+    let mut p = p;
+    let mut n = n;
+    let mut k = 0;
+
+    while n > 10 {
+        let a = 1 + n / 2;
+        let b = 1 + n - a;
+        let x = beta(rng, a as f64, b as f64);
+
+        if x >= p {
+            n = a - 1;
+            p /= x;
+        } else {
+            k += a;
+            n = b - 1;
+            p = (p - x) / (1.0 - x);
+        }
+    }
+
+    for _ in 0..n {
+        if rng.uniform() < p {
+            k += 1;
+        }
+    }
+    k
+}
+
+// multinomial
+
+// multinomial distributes n trials over the categories with the relative
+// probabilities p, which need not be normalized
+pub fn multinomial<R: Rng>(rng: &mut R, n: u64, p: &[f64]) -> Vec<u64> {
+    let norm: f64 = p.iter().sum();
+    let mut sum_p = 0.0;
+    let mut sum_n = 0;
+
+    p.iter()
+        .map(|&pk| {
+            let nk = if pk > 0.0 {
+                let q = (pk / (norm - sum_p)).min(1.0);
+                binomial(rng, q, n - sum_n)
+            } else {
+                0
+            };
+            sum_p += pk;
+            sum_n += nk;
+            nk
+        })
+        .collect()
+}
+
+// discrete
+
+// Discrete samples the indices of a table of weights in constant time with
+// the alias method, like gsl_ran_discrete. See M. D. Vose, "A Linear
+// Algorithm For Generating Random Numbers With a Given Distribution", IEEE
+// Trans. Softw. Eng., Vol 17, No 9, 1991.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrete {
+    // the normalized weights
+    p: Vec<f64>,
+    // the probability of keeping index k instead of using its alias
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl Discrete {
+    // new prepares the table for the non-negative weights, which need not be
+    // normalized
+    pub fn new(weights: &[f64]) -> Discrete {
+        let k = weights.len();
+        assert!(k > 0, "the table of weights must not be empty");
+        assert!(weights.iter().all(|&w| w >= 0.0), "weights must not be negative");
+
+        let total: f64 = weights.iter().sum();
+        assert!(total > 0.0, "the sum of the weights must be positive");
+
+        let p: Vec<f64> = weights.iter().map(|&w| w / total).collect();
+        let mut scaled: Vec<f64> = p.iter().map(|&q| q * k as f64).collect();
+        let mut prob = vec![1.0; k];
+        let mut alias: Vec<usize> = (0..k).collect();
+
+        let mut small: Vec<usize> = (0..k).filter(|&i| scaled[i] < 1.0).collect();
+        let mut large: Vec<usize> = (0..k).filter(|&i| scaled[i] >= 1.0).collect();
+
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            prob[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+
+        // whatever is left differs from one by rounding only
+        Discrete { p, prob, alias }
+    }
+
+    // sample returns an index drawn with probability proportional to its
+    // weight
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> usize {
+        let k = rng.uniform_int(self.prob.len() as u64) as usize;
+        if rng.uniform() < self.prob[k] {
+            k
+        } else {
+            self.alias[k]
+        }
+    }
+
+    // pdf returns the probability of index k
+    pub fn pdf(&self, k: usize) -> f64 {
+        self.p.get(k).cloned().unwrap_or(0.0)
+    }
+}
+
+// shuffle
+
+// shuffle puts the data in random order, every permutation being equally
+// likely, like gsl_ran_shuffle. See D. E. Knuth, "The Art of Computer
+// Programming", Vol 2, 3rd ed, Algorithm 3.4.2P.
+pub fn shuffle<T, R: Rng>(rng: &mut R, data: &mut [T]) {
+    for i in (1..data.len()).rev() {
+        let j = rng.uniform_int(i as u64 + 1) as usize;
+        data.swap(i, j);
+    }
+}
+
+// choose
+
+// choose returns k values of src without replacement, keeping their order
+// in src, like gsl_ran_choose. See D. E. Knuth, "The Art of Computer
+// Programming", Vol 2, 3rd ed, Algorithm 3.4.2S.
+pub fn choose<T: Clone, R: Rng>(rng: &mut R, src: &[T], k: usize) -> Vec<T> {
+    let n = src.len();
+    assert!(k <= n, "k is larger than the number of values to choose from");
+
+    let mut dest = Vec::with_capacity(k);
+    for (i, x) in src.iter().enumerate() {
+        if dest.len() == k {
+            break;
+        }
+        if (n - i) as f64 * rng.uniform() < (k - dest.len()) as f64 {
+            dest.push(x.clone());
+        }
+    }
+    dest
+}
+
+// sample
+
+// sample returns k values of src drawn with replacement, like gsl_ran_sample
+pub fn sample<T: Clone, R: Rng>(rng: &mut R, src: &[T], k: usize) -> Vec<T> {
+    assert!(!src.is_empty() || k == 0, "cannot sample from no values");
+
+    let n = src.len() as u64;
+    (0..k).map(|_| src[rng.uniform_int(n) as usize].clone()).collect()
+}
+
+// weighted_sample returns k values of src without replacement, where the
+// chance of every next value is proportional to its weight among those not
+// yet drawn. Every value gets the key u^(1/w), and the values with the k
+// largest keys are returned in the order of drawing, see P. S. Efraimidis
+// and P. G. Spirakis, "Weighted random sampling with a reservoir", Inf.
+// Process. Lett., Vol 97, No 5, 2006.
+pub fn weighted_sample<T: Clone, R: Rng>(rng: &mut R,
+                                         src: &[T],
+                                         weights: &[f64],
+                                         k: usize)
+                                         -> Vec<T> {
+    assert!(src.len() == weights.len(), "src and weights must have the same length");
+    assert!(weights.iter().all(|&w| w >= 0.0), "weights must not be negative");
+    assert!(k <= weights.iter().filter(|&&w| w > 0.0).count(),
+            "k is larger than the number of values with a positive weight");
+
+    // compare ln(u)/w instead of u^(1/w), which underflows for small weights
+    let mut keys: Vec<(f64, usize)> = weights.iter()
+        .enumerate()
+        .filter(|&(_, &w)| w > 0.0)
+        .map(|(i, &w)| (rng.uniform_pos().ln() / w, i))
+        .collect();
+
+    keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keys.iter().take(k).map(|&(_, i)| src[i].clone()).collect()
+}
+
+// reservoir
+
+// Reservoir keeps a uniform sample of k values of a stream of unknown
+// length, every value seen so far being in the sample with probability
+// k/n. See J. S. Vitter, "Random Sampling with a Reservoir", ACM Trans.
+// Math. Software, Vol 11, No 1, 1985, Algorithm R.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reservoir<T> {
+    k: usize,
+    seen: u64,
+    sample: Vec<T>,
+}
+
+impl<T> Reservoir<T> {
+    // new creates a reservoir for a sample of k values
+    pub fn new(k: usize) -> Reservoir<T> {
+        Reservoir {
+            k,
+            seen: 0,
+            sample: Vec::with_capacity(k),
+        }
+    }
+
+    // add offers the next value of the stream to the sample
+    pub fn add<R: Rng>(&mut self, rng: &mut R, x: T) {
+        self.seen += 1;
+        if self.sample.len() < self.k {
+            self.sample.push(x);
+            return;
+        }
+
+        let j = rng.uniform_int(self.seen) as usize;
+        if j < self.k {
+            self.sample[j] = x;
+        }
+    }
+
+    // seen returns the number of values offered so far
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    // sample returns the current sample, of min(k, seen) values
+    pub fn sample(&self) -> &[T] {
+        &self.sample
+    }
+
+    // into_sample consumes the reservoir, returning the sample
+    pub fn into_sample(self) -> Vec<T> {
+        self.sample
+    }
+}
@@ -0,0 +1,322 @@
+// rng.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 James Theiler, Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Seedable pseudo-random number generators, so that resampling and
+// simulation results can be reproduced.
+//
+// All generators implement the Rng trait. MT19937 follows gsl_rng_mt19937,
+// including its seeding and the GSL methods for uniform numbers, so that
+// its output matches GSL bit for bit.
+
+// Rng is a source of uniformly distributed random bits
+pub trait Rng {
+    // next_u64 returns the next 64 random bits
+    fn next_u64(&mut self) -> u64;
+
+    // uniform returns a double precision number uniformly distributed in
+    // [0, 1), made from the upper 53 random bits
+    fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    // uniform_pos returns a double precision number uniformly distributed in
+    // (0, 1), by skipping zero
+    fn uniform_pos(&mut self) -> f64 {
+        loop {
+            let x = self.uniform();
+            if x != 0.0 {
+                return x;
+            }
+        }
+    }
+
+    // uniform_int returns an integer uniformly distributed in [0, n), using
+    // the multiply and reject method of D. Lemire, "Fast Random Integer
+    // Generation in an Interval", 2019.
+    fn uniform_int(&mut self, n: u64) -> u64 {
+        lemire(self, n)
+    }
+}
+
+// Split is a generator that hands out independent generators, one for every
+// thread of a parallel computation
+pub trait Split: Rng + Sized {
+    // split returns a new generator and advances this one past it, so that
+    // the two don't share their sequences
+    fn split(&mut self) -> Self;
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+
+    fn uniform(&mut self) -> f64 {
+        (**self).uniform()
+    }
+
+    fn uniform_pos(&mut self) -> f64 {
+        (**self).uniform_pos()
+    }
+
+    fn uniform_int(&mut self, n: u64) -> u64 {
+        (**self).uniform_int(n)
+    }
+}
+
+fn lemire<R: Rng + ?Sized>(rng: &mut R, n: u64) -> u64 {
+    assert!(n > 0, "n must be larger than zero");
+
+    let threshold = n.wrapping_neg() % n;
+    loop {
+        let m = rng.next_u64() as u128 * n as u128;
+        if m as u64 >= threshold {
+            return (m >> 64) as u64;
+        }
+    }
+}
+
+// splitmix64 advances the state and returns the next output of SplitMix64,
+// used to expand a single seed into the state of a larger generator
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+// xoshiro256**
+
+// Xoshiro256StarStar is the xoshiro256** generator of D. Blackman and
+// S. Vigna, "Scrambled Linear Pseudorandom Number Generators", 2018.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoshiro256StarStar {
+    s: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    // new creates a generator whose state is filled from seed with SplitMix64
+    pub fn new(seed: u64) -> Xoshiro256StarStar {
+        let mut sm = seed;
+        Xoshiro256StarStar {
+            s: [splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm)],
+        }
+    }
+
+    // jump advances the generator by 2^128 steps, giving a sequence that
+    // doesn't overlap with the original for parallel computations
+    pub fn jump(&mut self) {
+        const JUMP: [u64; 4] = [0x180ec6d33cfd0aba, 0xd5a61266f0c9392c, 0xa9582618e03fc9aa,
+                                0x39abdc4529b1661c];
+
+        let mut s = [0u64; 4];
+        for &j in &JUMP {
+            for b in 0..64 {
+                if j & (1u64 << b) != 0 {
+                    for (si, &x) in s.iter_mut().zip(&self.s) {
+                        *si ^= x;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.s = s;
+    }
+}
+
+// split returns a copy of the generator and jumps this one ahead
+impl Split for Xoshiro256StarStar {
+    fn split(&mut self) -> Xoshiro256StarStar {
+        let rng = self.clone();
+        self.jump();
+        rng
+    }
+}
+
+impl Rng for Xoshiro256StarStar {
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+}
+
+// pcg64
+
+const PCG_MULTIPLIER_128: u128 = 0x2360ed051fc65da44385df649fccf645;
+const PCG_DEFAULT_INCREMENT_128: u128 = 0x5851f42d4c957f2d14057b7ef767814f;
+
+// Pcg64 is the PCG XSL RR 128/64 generator of M. E. O'Neill, "PCG: A Family
+// of Simple Fast Space-Efficient Statistically Good Algorithms for Random
+// Number Generation", 2014, as pcg64 of the PCG reference implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg64 {
+    state: u128,
+    inc: u128,
+}
+
+impl Pcg64 {
+    // new creates a generator on the default stream of the reference
+    // implementation
+    pub fn new(seed: u64) -> Pcg64 {
+        Pcg64::with_stream(seed as u128, PCG_DEFAULT_INCREMENT_128 >> 1)
+    }
+
+    // with_stream creates a generator like pcg64_srandom_r(initstate, initseq),
+    // generators on different streams give independent sequences
+    pub fn with_stream(initstate: u128, initseq: u128) -> Pcg64 {
+        let mut rng = Pcg64 {
+            state: 0,
+            inc: (initseq << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(initstate);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(PCG_MULTIPLIER_128).wrapping_add(self.inc);
+    }
+}
+
+// split returns a generator on a stream with a random state and increment
+impl Split for Pcg64 {
+    fn split(&mut self) -> Pcg64 {
+        let state = (self.next_u64() as u128) << 64 | self.next_u64() as u128;
+        let seq = (self.next_u64() as u128) << 64 | self.next_u64() as u128;
+        Pcg64::with_stream(state, seq)
+    }
+}
+
+impl Rng for Pcg64 {
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        let xsl = ((self.state >> 64) as u64) ^ (self.state as u64);
+        xsl.rotate_right((self.state >> 122) as u32)
+    }
+}
+
+// mt19937
+
+const MT_N: usize = 624;
+const MT_M: usize = 397;
+const MT_UPPER_MASK: u32 = 0x80000000;
+const MT_LOWER_MASK: u32 = 0x7fffffff;
+const MT_MATRIX_A: u32 = 0x9908b0df;
+
+// MT19937 is the Mersenne Twister of M. Matsumoto and T. Nishimura,
+// "Mersenne Twister: A 623-dimensionally equidistributed uniform
+// pseudorandom number generator", ACM Trans. Model. Comput. Simul., Vol 8,
+// No 1, 1998, as gsl_rng_mt19937.
+#[derive(Clone)]
+pub struct MT19937 {
+    mt: [u32; MT_N],
+    mti: usize,
+}
+
+impl MT19937 {
+    // new creates a generator seeded like gsl_rng_set, a seed of zero is
+    // replaced by the GSL default seed 4357
+    pub fn new(seed: u32) -> MT19937 {
+        let seed = if seed == 0 { 4357 } else { seed };
+
+        let mut mt = [0u32; MT_N];
+        mt[0] = seed;
+        for i in 1..MT_N {
+            // See Knuth's "Art of Computer Programming" Vol. 2, 3rd Ed. p.106
+            // for the multiplier
+            mt[i] = 1812433253u32.wrapping_mul(mt[i - 1] ^ (mt[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+
+        MT19937 { mt, mti: MT_N }
+    }
+
+    // get returns the next 32 random bits, like gsl_rng_get
+    pub fn get(&mut self) -> u32 {
+        if self.mti >= MT_N {
+            // generate N words at one time
+            for kk in 0..MT_N {
+                let y = (self.mt[kk] & MT_UPPER_MASK) | (self.mt[(kk + 1) % MT_N] & MT_LOWER_MASK);
+                let mag = if y & 1 != 0 { MT_MATRIX_A } else { 0 };
+                self.mt[kk] = self.mt[(kk + MT_M) % MT_N] ^ (y >> 1) ^ mag;
+            }
+            self.mti = 0;
+        }
+
+        let mut k = self.mt[self.mti];
+        k ^= k >> 11;
+        k ^= (k << 7) & 0x9d2c5680;
+        k ^= (k << 15) & 0xefc60000;
+        k ^= k >> 18;
+
+        self.mti += 1;
+        k
+    }
+}
+
+// split returns a generator seeded with the next 32 random bits
+impl Split for MT19937 {
+    fn split(&mut self) -> MT19937 {
+        MT19937::new(self.get())
+    }
+}
+
+impl Rng for MT19937 {
+    fn next_u64(&mut self) -> u64 {
+        let hi = self.get() as u64;
+        let lo = self.get() as u64;
+        (hi << 32) | lo
+    }
+
+    // uniform returns get() / 2^32, like gsl_rng_uniform
+    fn uniform(&mut self) -> f64 {
+        self.get() as f64 / 4294967296.0
+    }
+
+    // uniform_int scales get() down like gsl_rng_uniform_int, values of n
+    // beyond the range of get() use 64 random bits instead
+    fn uniform_int(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "n must be larger than zero");
+
+        let range = u32::MAX as u64;
+        if n > range {
+            return lemire(self, n);
+        }
+
+        let scale = range / n;
+        loop {
+            let k = self.get() as u64 / scale;
+            if k < n {
+                return k;
+            }
+        }
+    }
+}
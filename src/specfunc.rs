@@ -0,0 +1,245 @@
+// specfunc.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 Gerard Jungman, Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Special functions needed internally by the statistics routines.
+
+use std::f64::consts::PI;
+
+// Lanczos coefficients for g = 7, n = 9
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [0.9999999999998099,
+                           676.5203681218851,
+                           -1259.1392167224028,
+                           771.3234287776531,
+                           -176.6150291621406,
+                           12.507343278686905,
+                           -0.13857109526572012,
+                           9.984369578019572e-6,
+                           1.5056327351493116e-7];
+
+// ln_gamma calculates the logarithm of the gamma function for x > 0, using
+// the Lanczos approximation and the reflection formula for x < 0.5
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        return (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let mut a = LANCZOS[0];
+    let t = x + LANCZOS_G + 0.5;
+    for (i, &c) in LANCZOS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+// erfc calculates the complementary error function. The power series of erf
+// is used for |x| < 2 and the continued fraction of erfc otherwise.
+pub fn erfc(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+
+    if x < 2.0 {
+        // erf(x) = 2/sqrt(pi) Sum_n (-1)^n x^(2n+1) / (n! (2n+1))
+        let x2 = x * x;
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term.abs() > 1.0e-17 * sum.abs() {
+            n += 1.0;
+            term *= -x2 / n;
+            sum += term / (2.0 * n + 1.0);
+        }
+        return 1.0 - 2.0 / PI.sqrt() * sum;
+    }
+
+    // erfc(x) = exp(-x^2)/sqrt(pi) 1/(x + (1/2)/(x + 1/(x + (3/2)/(x + ...))))
+    // evaluated with the modified Lentz method
+    let tiny = 1.0e-300;
+    let mut f = x;
+    let mut c = x;
+    let mut d = 0.0;
+    for i in 1..500 {
+        let a = i as f64 / 2.0;
+        d = x + a * d;
+        d = if d == 0.0 { tiny } else { d };
+        c = x + a / c;
+        c = if c == 0.0 { tiny } else { c };
+        d = 1.0 / d;
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < 1.0e-16 {
+            break;
+        }
+    }
+    (-x * x).exp() / PI.sqrt() / f
+}
+
+// normal_cdf calculates the cumulative distribution function of the
+// standard normal distribution
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / 2.0f64.sqrt())
+}
+
+// normal_quantile calculates the inverse of normal_cdf, using the rational
+// approximation of P. J. Acklam refined with one step of Halley's method
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e+01,
+                         2.209460984245205e+02,
+                         -2.759285104469687e+02,
+                         1.38357751867269e+02,
+                         -3.066479806614716e+01,
+                         2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01,
+                         1.615858368580409e+02,
+                         -1.556989798598866e+02,
+                         6.680131188771972e+01,
+                         -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03,
+                         -3.223964580411365e-01,
+                         -2.400758277161838e+00,
+                         -2.549732539343734e+00,
+                         4.374664141464968e+00,
+                         2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03,
+                         3.224671290700398e-01,
+                         2.445134137142996e+00,
+                         3.754408661907416e+00];
+    const P_LOW: f64 = 0.02425;
+
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let x = if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+        ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+        (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+        ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    let e = normal_cdf(x) - p;
+    let u = e * (2.0 * PI).sqrt() * (x * x / 2.0).exp();
+    x - u / (1.0 + x * u / 2.0)
+}
+
+// beta_inc calculates the regularized incomplete beta function I_x(a, b),
+// using the continued fraction of W. H. Press et al, "Numerical Recipes",
+// 3rd ed, section 6.4, on the side of x where it converges fast
+pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() +
+                 b * (-x).ln_1p())
+        .exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_cf(a, b, x) / a
+    } else {
+        1.0 - front * beta_cf(b, a, 1.0 - x) / b
+    }
+}
+
+// beta_cf evaluates the continued fraction of beta_inc with the modified
+// Lentz method
+fn beta_cf(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = 1.0e-300;
+    let guard = |v: f64| if v.abs() < tiny { tiny } else { v };
+
+    let mut c = 1.0;
+    let mut d = 1.0 / guard(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..1000 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // the even step
+        let aa = m * (b - m) * x / ((a - 1.0 + m2) * (a + m2));
+        d = 1.0 / guard(1.0 + aa * d);
+        c = guard(1.0 + aa / c);
+        h *= d * c;
+
+        // the odd step
+        let aa = -(a + m) * (a + b + m) * x / ((a + m2) * (a + 1.0 + m2));
+        d = 1.0 / guard(1.0 + aa * d);
+        c = guard(1.0 + aa / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1.0e-16 {
+            break;
+        }
+    }
+    h
+}
+
+// t_sf calculates the upper tail probability P(T > t) of Student's t
+// distribution with nu degrees of freedom
+pub fn t_sf(t: f64, nu: f64) -> f64 {
+    let tail = 0.5 * beta_inc(nu / 2.0, 0.5, nu / (nu + t * t));
+    if t > 0.0 { tail } else { 1.0 - tail }
+}
+
+// t_isf calculates the inverse of t_sf for upper tail probabilities q <= 1/2
+// by bisection, which is slow but dependable for every nu
+pub fn t_isf(q: f64, nu: f64) -> f64 {
+    assert!(q > 0.0 && q <= 0.5, "q must lie in (0, 1/2]");
+
+    let mut lo = 0.0;
+    let mut hi = 1.0;
+    while t_sf(hi, nu) > q {
+        lo = hi;
+        hi *= 2.0;
+    }
+
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if t_sf(mid, nu) > q {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
@@ -0,0 +1,127 @@
+// statistics.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// The statistics functions as methods of the data:
+//
+//     let m = data.mean();
+//     let q = data.quantile(0.9);
+//     let wm = data.w_mean(&weights);
+//
+// Statistics is implemented for everything the functions accept, slices,
+// arrays, vectors and strided slices, and every method calls the function of
+// the same name. Only min and max are called minimum and maximum, because
+// Ord::min and Ord::max would take precedence over them for integer data.
+
+use data::Data;
+use types::F64;
+use {kurtosis, max, mean, median_from_sorted_data, min, quantile_from_sorted_data, sd, skew,
+     sorted_f64, variance, w_kurtosis, w_mean, w_sd, w_skew, w_variance};
+
+pub trait Statistics<T: F64> {
+    fn mean(&self) -> f64;
+
+    fn variance(&self) -> f64;
+
+    fn sd(&self) -> f64;
+
+    fn skew(&self) -> f64;
+
+    fn kurtosis(&self) -> f64;
+
+    // minimum returns the first smallest value and its position
+    fn minimum(&self) -> (f64, usize);
+
+    // maximum returns the first largest value and its position
+    fn maximum(&self) -> (f64, usize);
+
+    // median returns the median, sorting a copy of the data
+    fn median(&self) -> f64;
+
+    // quantile returns the quantile f like quantile_from_sorted_data, sorting
+    // a copy of the data
+    fn quantile(&self, f: f64) -> f64;
+
+    fn w_mean<W: Data<T> + ?Sized>(&self, w: &W) -> f64;
+
+    fn w_variance<W: Data<T> + ?Sized>(&self, w: &W) -> f64;
+
+    fn w_sd<W: Data<T> + ?Sized>(&self, w: &W) -> f64;
+
+    fn w_skew<W: Data<T> + ?Sized>(&self, w: &W) -> f64;
+
+    fn w_kurtosis<W: Data<T> + ?Sized>(&self, w: &W) -> f64;
+}
+
+impl<T: F64, D: Data<T> + ?Sized> Statistics<T> for D {
+    fn mean(&self) -> f64 {
+        mean(self)
+    }
+
+    fn variance(&self) -> f64 {
+        variance(self)
+    }
+
+    fn sd(&self) -> f64 {
+        sd(self)
+    }
+
+    fn skew(&self) -> f64 {
+        skew(self)
+    }
+
+    fn kurtosis(&self) -> f64 {
+        kurtosis(self)
+    }
+
+    fn minimum(&self) -> (f64, usize) {
+        min(self)
+    }
+
+    fn maximum(&self) -> (f64, usize) {
+        max(self)
+    }
+
+    fn median(&self) -> f64 {
+        median_from_sorted_data(&sorted_f64(self))
+    }
+
+    fn quantile(&self, f: f64) -> f64 {
+        quantile_from_sorted_data(&sorted_f64(self), f)
+    }
+
+    fn w_mean<W: Data<T> + ?Sized>(&self, w: &W) -> f64 {
+        w_mean(w, self)
+    }
+
+    fn w_variance<W: Data<T> + ?Sized>(&self, w: &W) -> f64 {
+        w_variance(w, self)
+    }
+
+    fn w_sd<W: Data<T> + ?Sized>(&self, w: &W) -> f64 {
+        w_sd(w, self)
+    }
+
+    fn w_skew<W: Data<T> + ?Sized>(&self, w: &W) -> f64 {
+        w_skew(w, self)
+    }
+
+    fn w_kurtosis<W: Data<T> + ?Sized>(&self, w: &W) -> f64 {
+        w_kurtosis(w, self)
+    }
+}
@@ -0,0 +1,245 @@
+// summary.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+// Descriptive summaries of a dataset and box plot statistics.
+//
+// describe finds the common statistics in two passes over the data, where
+// calling mean, sd, skew and kurtosis separately rescans the data for every
+// statistic, and again for the mean and sd inside skew and kurtosis. The
+// results agree with those functions up to rounding.
+
+use std::fmt;
+
+use data::Data;
+use types::F64;
+use {median_from_sorted_data, quantile_from_sorted_data, sorted_f64};
+
+// Summary holds the descriptive statistics of a dataset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub n: usize,
+    pub mean: f64,
+    // the sample variance, with n - 1 in the denominator like variance
+    pub variance: f64,
+    pub sd: f64,
+    // the standard error of the mean, sd / sqrt(n)
+    pub sem: f64,
+    pub skew: f64,
+    // the excess kurtosis, zero for a Gaussian
+    pub kurtosis: f64,
+    pub min: f64,
+    pub max: f64,
+    // max - min
+    pub range: f64,
+    pub sum: f64,
+}
+
+// describe summarizes the data. The first pass finds the sum, mean, min and
+// max, the second the central moments with the same recurrence relations as
+// variance, skew and kurtosis. Like min and max, a NaN in the data makes min
+// and max NaN.
+pub fn describe<T: F64, D: Data<T> + ?Sized>(data: &D) -> Summary {
+    assert!(!data.is_empty(), "describe needs at least one value");
+
+    let mut sum = 0.0;
+    let mut mean = 0.0;
+    let mut min = data.at(0).f64();
+    let mut max = min;
+    let mut nan = false;
+
+    for (i, val) in data.iter().enumerate() {
+        let xi = val.f64();
+        sum += xi;
+        mean += (xi - mean) / (i + 1) as f64;
+        if xi < min {
+            min = xi;
+        }
+        if xi > max {
+            max = xi;
+        }
+        nan |= xi.is_nan();
+    }
+
+    if nan {
+        min = f64::NAN;
+        max = f64::NAN;
+    }
+
+    // the second, third and fourth central moments
+    let mut m2 = 0.0;
+    let mut m3 = 0.0;
+    let mut m4 = 0.0;
+
+    for (i, val) in data.iter().enumerate() {
+        let delta = val.f64() - mean;
+        let delta2 = delta * delta;
+        m2 += (delta2 - m2) / (i + 1) as f64;
+        m3 += (delta2 * delta - m3) / (i + 1) as f64;
+        m4 += (delta2 * delta2 - m4) / (i + 1) as f64;
+    }
+
+    let n = data.len() as f64;
+    let variance = m2 * n / (n - 1.0);
+    let sd = variance.sqrt();
+
+    Summary {
+        n: data.len(),
+        mean,
+        variance,
+        sd,
+        sem: sd / n.sqrt(),
+        skew: m3 / (sd * sd * sd),
+        kurtosis: m4 / (variance * variance) - 3.0,
+        min,
+        max,
+        range: max - min,
+        sum,
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<10}{:>16}", "n", self.n)?;
+
+        let rows = [("mean", self.mean),
+                    ("variance", self.variance),
+                    ("sd", self.sd),
+                    ("sem", self.sem),
+                    ("skew", self.skew),
+                    ("kurtosis", self.kurtosis),
+                    ("min", self.min),
+                    ("max", self.max),
+                    ("range", self.range),
+                    ("sum", self.sum)];
+
+        for &(name, value) in &rows {
+            // use exponents for values that would lose their digits
+            if value != 0.0 && (value.abs() >= 1.0e9 || value.abs() < 1.0e-4) {
+                writeln!(f, "{:<10}{:>16.6e}", name, value)?;
+            } else {
+                writeln!(f, "{:<10}{:>16.6}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// five number summary
+
+// FiveNumberSummary holds the minimum, the quartiles and the maximum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiveNumberSummary {
+    pub min: f64,
+    // the lower quartile
+    pub q1: f64,
+    pub median: f64,
+    // the upper quartile
+    pub q3: f64,
+    pub max: f64,
+}
+
+impl FiveNumberSummary {
+    fn from_sorted(sorted: &[f64]) -> FiveNumberSummary {
+        FiveNumberSummary {
+            min: sorted[0],
+            q1: quantile_from_sorted_data(sorted, 0.25),
+            median: median_from_sorted_data(sorted),
+            q3: quantile_from_sorted_data(sorted, 0.75),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+
+    // iqr returns the interquartile range q3 - q1
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+}
+
+// five_number_summary finds the minimum, quartiles and maximum of the data,
+// the quartiles interpolated like quantile_from_sorted_data. The data must
+// not contain NaN.
+pub fn five_number_summary<T: F64, D: Data<T> + ?Sized>(data: &D) -> FiveNumberSummary {
+    assert!(!data.is_empty(), "five_number_summary needs at least one value");
+    FiveNumberSummary::from_sorted(&sorted_f64(data))
+}
+
+// boxplot
+
+// BoxplotStats holds what is needed to draw a box plot
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxplotStats {
+    pub summary: FiveNumberSummary,
+    pub iqr: f64,
+    // the Tukey fences q1 - k iqr and q3 + k iqr
+    pub lower_fence: f64,
+    pub upper_fence: f64,
+    // the most extreme values inside the fences, where the whiskers end
+    pub lower_whisker: f64,
+    pub upper_whisker: f64,
+    // the indices of the values outside the fences, in the order of the data
+    pub outliers: Vec<usize>,
+}
+
+// boxplot_stats finds the five number summary of the data, the fences at
+// whisker_factor times the iqr from the quartiles, usually 1.5, and the
+// values outside the fences. The data must not contain NaN. When infinite
+// values leave the iqr undefined the fences and whiskers are NaN and no value
+// is an outlier.
+pub fn boxplot_stats<T, D>(data: &D, whisker_factor: f64) -> BoxplotStats
+    where T: F64,
+          D: Data<T> + ?Sized
+{
+    assert!(!data.is_empty(), "boxplot_stats needs at least one value");
+    assert!(whisker_factor >= 0.0, "whisker_factor must not be negative");
+
+    let sorted = sorted_f64(data);
+    let summary = FiveNumberSummary::from_sorted(&sorted);
+    let iqr = summary.iqr();
+    let lower_fence = summary.q1 - whisker_factor * iqr;
+    let upper_fence = summary.q3 + whisker_factor * iqr;
+
+    // the quartiles lie inside the fences, so both whiskers exist unless the
+    // fences are NaN
+    let lo = sorted.partition_point(|&x| x < lower_fence);
+    let hi = sorted.partition_point(|&x| x <= upper_fence);
+    let (lower_whisker, upper_whisker) = if lo < hi {
+        (sorted[lo], sorted[hi - 1])
+    } else {
+        (f64::NAN, f64::NAN)
+    };
+
+    let outliers = data.iter()
+        .enumerate()
+        .filter(|&(_, x)| {
+            let x = x.f64();
+            x < lower_fence || x > upper_fence
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    BoxplotStats {
+        summary,
+        iqr,
+        lower_fence,
+        upper_fence,
+        lower_whisker,
+        upper_whisker,
+        outliers,
+    }
+}
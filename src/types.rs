@@ -18,6 +18,12 @@
 // Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
 //
 
+use std::num::{NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+               NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping};
+use std::time::Duration;
+
+// F64 converts the values of a dataset to f64. Integers beyond 2^53 are
+// rounded to the nearest f64.
 pub trait F64 {
     fn f64(&self) -> f64;
 }
@@ -46,6 +52,12 @@ impl F64 for i64 {
     }
 }
 
+impl F64 for i128 {
+    fn f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
 impl F64 for u8 {
     fn f64(&self) -> f64 {
         *self as f64
@@ -69,6 +81,12 @@ impl F64 for u64 {
         *self as f64
     }
 }
+impl F64 for u128 {
+    fn f64(&self) -> f64 {
+        *self as f64
+    }
+}
+
 impl F64 for isize {
     fn f64(&self) -> f64 {
         *self as f64
@@ -91,3 +109,90 @@ impl F64 for f64 {
         *self as f64
     }
 }
+
+// true counts as 1 and false as 0, so the mean is the fraction of true values
+impl F64 for bool {
+    fn f64(&self) -> f64 {
+        if *self { 1.0 } else { 0.0 }
+    }
+}
+
+// a duration counts as its length in seconds
+impl F64 for Duration {
+    fn f64(&self) -> f64 {
+        self.as_secs_f64()
+    }
+}
+
+// a missing value counts as NaN, which nan::NanPolicy can omit; see missing
+// for skipping them instead
+impl<T: F64> F64 for Option<T> {
+    fn f64(&self) -> f64 {
+        match *self {
+            Some(ref x) => x.f64(),
+            None => f64::NAN,
+        }
+    }
+}
+
+impl<T: F64> F64 for Wrapping<T> {
+    fn f64(&self) -> f64 {
+        self.0.f64()
+    }
+}
+
+impl<T: F64 + ?Sized> F64 for &T {
+    fn f64(&self) -> f64 {
+        (**self).f64()
+    }
+}
+
+impl<T: F64 + ?Sized> F64 for Box<T> {
+    fn f64(&self) -> f64 {
+        (**self).f64()
+    }
+}
+
+macro_rules! impl_f64_nonzero {
+    ($($t:ty),*) => {
+        $(
+            impl F64 for $t {
+                fn f64(&self) -> f64 {
+                    self.get() as f64
+                }
+            }
+        )*
+    }
+}
+
+impl_f64_nonzero!(NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+                  NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+
+// impl_f64 implements F64 for types that convert into f64, such as newtypes
+// of measurements, so they can be passed to the statistics functions:
+//
+//     #[derive(Clone)]
+//     struct Meters(f64);
+//
+//     impl From<Meters> for f64 {
+//         fn from(m: Meters) -> f64 {
+//             m.0
+//         }
+//     }
+//
+//     impl_f64!(Meters);
+//
+// A blanket implementation for every Into<f64> type isn't possible, since it
+// would overlap with the implementations above.
+#[macro_export]
+macro_rules! impl_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl $crate::types::F64 for $t {
+                fn f64(&self) -> f64 {
+                    ::std::convert::Into::<f64>::into(::std::clone::Clone::clone(self))
+                }
+            }
+        )*
+    }
+}
@@ -0,0 +1,217 @@
+// accuracy_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+
+use stat::accuracy::{Accuracy, ExactSum, NeumaierSum};
+
+#[test]
+fn test_neumaier_sum() {
+    let mut sum = NeumaierSum::new();
+    for &x in &[1.0, 1e100, 1.0, -1e100] {
+        sum.add(x);
+    }
+    gsl::test(sum.sum() != 2.0, "NeumaierSum cancellation");
+
+    let mut sum = NeumaierSum::default();
+    for _ in 0..10 {
+        sum.add(0.1);
+    }
+    gsl::test(sum.sum() != 1.0, "NeumaierSum of tenths");
+}
+
+#[test]
+fn test_accuracy() {
+    // values far from zero relative to their spread
+    let data: Vec<f64> = (0..10000).map(|i| 1e9 + (i * 7919 % 1000) as f64 / 1000.0).collect();
+    let w: Vec<f64> = (0..10000).map(|i| (1 + i % 3) as f64).collect();
+
+    let expected_mean = 1000000000.4995;
+    let expected_tss = 833.3325000190853;
+    let expected_variance = 0.08334158416032457;
+
+    let mean = stat::mean_with(&data, Accuracy::Compensated);
+    gsl::test_rel(mean, expected_mean, 1e-15, "compensated mean");
+
+    let tss = stat::tss_with(&data, Accuracy::Compensated);
+    gsl::test_rel(tss, expected_tss, 1e-14, "compensated tss");
+
+    // about the mean rounded to f64
+    let tss = stat::tss_mean_with(&data, expected_mean, Accuracy::Compensated);
+    gsl::test_rel(tss, 833.3325000190985, 1e-14, "compensated tss_mean");
+
+    let variance = stat::variance_with(&data, Accuracy::Compensated);
+    gsl::test_rel(variance, expected_variance, 1e-14, "compensated variance");
+
+    let variance = stat::variance_mean_with(&data, expected_mean, Accuracy::Compensated);
+    gsl::test_rel(variance, 0.08334158416032587, 1e-14, "compensated variance_mean");
+
+    let sd = stat::sd_with(&data, Accuracy::Compensated);
+    gsl::test_rel(sd, expected_variance.sqrt(), 1e-14, "compensated sd");
+
+    // the plain sums lose several digits
+    let variance = stat::variance_with(&data, Accuracy::Fast);
+    gsl::test(variance != stat::variance(&data), "fast variance");
+    gsl::test_rel(variance, expected_variance, 1e-9, "fast variance");
+
+    let expected_wmean = 1000000000.4995236;
+    let expected_wtss = 1666.3366486037005;
+    let expected_wvariance = 0.08333072060583638;
+
+    let wmean = stat::w_mean_with(&w, &data, Accuracy::Compensated);
+    gsl::test_rel(wmean, expected_wmean, 1e-15, "compensated w_mean");
+
+    let wtss = stat::w_tss_with(&w, &data, Accuracy::Compensated);
+    gsl::test_rel(wtss, expected_wtss, 1e-14, "compensated w_tss");
+
+    let wtss = stat::w_tss_mean_with(&w, &data, expected_wmean, Accuracy::Compensated);
+    gsl::test_rel(wtss, 1666.3366486037041, 1e-14, "compensated w_tss_mean");
+
+    let wvariance = stat::w_variance_with(&w, &data, Accuracy::Compensated);
+    gsl::test_rel(wvariance, expected_wvariance, 1e-14, "compensated w_variance");
+
+    let wvariance = stat::w_variance_mean_with(&w, &data, expected_wmean, Accuracy::Compensated);
+    gsl::test_rel(wvariance, 0.08333072060583656, 1e-14, "compensated w_variance_mean");
+
+    let wsd = stat::w_sd_with(&w, &data, Accuracy::Compensated);
+    gsl::test_rel(wsd, expected_wvariance.sqrt(), 1e-14, "compensated w_sd");
+
+    let wsd = stat::w_sd_mean_with(&w, &data, expected_wmean, Accuracy::Compensated);
+    gsl::test_rel(wsd, 0.08333072060583656f64.sqrt(), 1e-14, "compensated w_sd_mean");
+
+    let wtss = stat::w_tss_with(&w, &data, Accuracy::Fast);
+    gsl::test(wtss != stat::w_tss(&w, &data), "fast w_tss");
+}
+
+#[test]
+fn test_exact_sum() {
+    let mut sum = ExactSum::new();
+    for &x in &[1e100, 1.0, -1e100, 1e-100, 1e50, -1.0, -1e50] {
+        sum.add(x);
+    }
+    gsl::test(sum.sum() != 1e-100, "ExactSum cancellation");
+
+    // the second 1e-16 breaks the tie of the first
+    let mut sum = ExactSum::new();
+    for &x in &[1.0, 1e-16, 1e-16] {
+        sum.add(x);
+    }
+    gsl::test(sum.sum() != 1.0000000000000002, "ExactSum tie");
+
+    let mut sum = ExactSum::default();
+    sum.add(3.0);
+    sum.add_product(0.1, 0.1);
+    sum.add(-3.0);
+    gsl::test(sum.sum() != 0.1 * 0.1 + (0.1f64).mul_add(0.1, -0.1 * 0.1),
+              "ExactSum add_product");
+
+    sum.add(f64::INFINITY);
+    gsl::test(sum.sum() != f64::INFINITY, "ExactSum infinity");
+
+    let empty = ExactSum::new();
+    gsl::test(empty.sum() != 0.0, "ExactSum empty");
+}
+
+#[test]
+fn test_exact() {
+    // the exact sum 2.8 divided by 7 rounds to 0.4, unlike the rounded sum
+    let data = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7];
+    gsl::test(stat::mean_with(&data, Accuracy::Exact) != 0.4, "exact mean rounding");
+
+    let data = [1.995256175932014e-06, 8.97790986260546, -0.04167752025951874];
+    let mean = stat::mean_with(&data, Accuracy::Exact);
+    gsl::test(mean != 2.9787447792007056, "exact mean rounding of mixed sizes");
+
+    let data: Vec<f64> = (0..10000).map(|i| 1e9 + (i * 7919 % 1000) as f64 / 1000.0).collect();
+    let w: Vec<f64> = (0..10000).map(|i| (1 + i % 3) as f64).collect();
+    let mut reversed = data.clone();
+    reversed.reverse();
+    let mut w_reversed = w.clone();
+    w_reversed.reverse();
+
+    let mean = stat::mean_with(&data, Accuracy::Exact);
+    gsl::test(mean != 1000000000.4995, "exact mean");
+    gsl::test(mean != stat::mean_with(&reversed, Accuracy::Exact), "exact mean reversed");
+
+    let tss = stat::tss_with(&data, Accuracy::Exact);
+    gsl::test_rel(tss, 833.3325000190853, 1e-15, "exact tss");
+    gsl::test(tss != stat::tss_with(&reversed, Accuracy::Exact), "exact tss reversed");
+
+    let tss = stat::tss_mean_with(&data, 1000000000.4995, Accuracy::Exact);
+    gsl::test(tss != 833.3325000190985, "exact tss_mean");
+
+    let variance = stat::variance_with(&data, Accuracy::Exact);
+    gsl::test_rel(variance, 0.08334158416032457, 1e-15, "exact variance");
+    gsl::test(variance != stat::variance_with(&reversed, Accuracy::Exact),
+              "exact variance reversed");
+
+    let variance = stat::variance_mean_with(&data, 1000000000.4995, Accuracy::Exact);
+    gsl::test_rel(variance, 0.08334158416032587, 1e-15, "exact variance_mean");
+
+    let sd = stat::sd_with(&data, Accuracy::Exact);
+    gsl::test_rel(sd, 0.08334158416032457f64.sqrt(), 1e-15, "exact sd");
+
+    let wmean = stat::w_mean_with(&w, &data, Accuracy::Exact);
+    gsl::test(wmean != 1000000000.4995236, "exact w_mean");
+    gsl::test(wmean != stat::w_mean_with(&w_reversed, &reversed, Accuracy::Exact),
+              "exact w_mean reversed");
+
+    let wtss = stat::w_tss_with(&w, &data, Accuracy::Exact);
+    gsl::test_rel(wtss, 1666.3366486037005, 1e-15, "exact w_tss");
+
+    let wtss = stat::w_tss_mean_with(&w, &data, 1000000000.4995236, Accuracy::Exact);
+    gsl::test(wtss != 1666.3366486037041, "exact w_tss_mean");
+
+    let wvariance = stat::w_variance_with(&w, &data, Accuracy::Exact);
+    gsl::test_rel(wvariance, 0.08333072060583638, 1e-15, "exact w_variance");
+    gsl::test(wvariance != stat::w_variance_with(&w_reversed, &reversed, Accuracy::Exact),
+              "exact w_variance reversed");
+
+    let wsd = stat::w_sd_mean_with(&w, &data, 1000000000.4995236, Accuracy::Exact);
+    gsl::test_rel(wsd, 0.08333072060583656f64.sqrt(), 1e-15, "exact w_sd_mean");
+
+    {
+        // one weight dominates, so (Sum w)^2 - Sum w^2 cancels
+        let w = [1e8, 0.1, 0.1, 0.1];
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let w_permuted = [0.1, 0.1, 1e8, 0.1];
+        let permuted = [2.0, 3.0, 1.0, 4.0];
+
+        let wvariance = stat::w_variance_with(&w, &data, Accuracy::Exact);
+        gsl::test_rel(wvariance, 2.333333332, 1e-15, "exact w_variance dominant weight");
+        gsl::test(wvariance != stat::w_variance_with(&w_permuted, &permuted, Accuracy::Exact),
+                  "exact w_variance dominant weight permuted");
+
+        let wmean = stat::w_mean_with(&w, &data, Accuracy::Exact);
+        let wvariance = stat::w_variance_mean_with(&w, &data, wmean, Accuracy::Exact);
+        gsl::test(wvariance !=
+                  stat::w_variance_mean_with(&w_permuted, &permuted, wmean, Accuracy::Exact),
+                  "exact w_variance_mean dominant weight permuted");
+
+        let wvariance = stat::w_variance_with(&w, &data, Accuracy::Compensated);
+        gsl::test_rel(wvariance, 2.333333332, 1e-15, "compensated w_variance dominant weight");
+        let wvariance = stat::w_variance_with(&w_permuted, &permuted, Accuracy::Compensated);
+        gsl::test_rel(wvariance,
+                      2.333333332,
+                      1e-15,
+                      "compensated w_variance dominant weight permuted");
+    }
+}
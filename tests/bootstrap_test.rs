@@ -0,0 +1,120 @@
+// bootstrap_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::bootstrap::bootstrap;
+use stat::rng::{Rng, Xoshiro256StarStar};
+
+#[test]
+fn test_xoshiro256starstar() {
+    let mut rng = Xoshiro256StarStar::new(42);
+    let expected: [u64; 3] = [1546998764402558742, 6990951692964543102, 12544586762248559009];
+
+    for &e in &expected {
+        let r = rng.next_u64();
+        let str = format!("next_u64 ({} observed vs {} expected)", r, e);
+        gsl::test(r != e, &str);
+    }
+
+    {
+        let mut rng = Xoshiro256StarStar::new(42);
+        rng.jump();
+        let expected: [u64; 2] = [5766981335298035530, 13414075677763163907];
+        for &e in &expected {
+            let r = rng.next_u64();
+            let str = format!("jump ({} observed vs {} expected)", r, e);
+            gsl::test(r != e, &str);
+        }
+    }
+
+    for _ in 0..1000 {
+        let u = rng.uniform();
+        gsl::test(!(0.0..1.0).contains(&u), "uniform in [0, 1)");
+        let k = rng.uniform_int(7);
+        gsl::test(k >= 7, "uniform_int in [0, 7)");
+    }
+}
+
+#[test]
+fn test_bootstrap() {
+    let slice_a = &[0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                    0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+
+    let mut rng = Xoshiro256StarStar::new(1);
+    let b = bootstrap(slice_a, mean, 4000, &mut rng);
+
+    {
+        gsl::test_rel(b.estimate, 0.0728, 1.0e-10, "bootstrap estimate");
+        gsl::test(b.replicates.len() != 4000, "bootstrap replicates");
+        gsl::test(b.replicates.windows(2).any(|w| w[0] > w[1]),
+                  "bootstrap replicates sorted");
+    }
+
+    {
+        // the standard error of the mean is sd / sqrt(n) times sqrt((n-1)/n)
+        let n = slice_a.len() as f64;
+        let expected = sd(slice_a) / n.sqrt() * ((n - 1.0) / n).sqrt();
+        gsl::test_rel(b.std_error, expected, 0.05, "bootstrap std_error");
+        gsl::test(b.bias.abs() > 0.1 * b.std_error, "bootstrap bias");
+    }
+
+    for &level in &[0.8, 0.9, 0.95] {
+        let (pl, pu) = b.percentile_interval(level);
+        let (bl, bu) = b.basic_interval(level);
+        let (cl, cu) = b.bca_interval(level);
+
+        let str = format!("intervals at {} contain the estimate", level);
+        gsl::test(!(pl < b.estimate && b.estimate < pu), &str);
+        gsl::test(!(bl < b.estimate && b.estimate < bu), &str);
+        gsl::test(!(cl < b.estimate && b.estimate < cu), &str);
+
+        // for the mean all intervals are close to the normal interval
+        let half = 1.96 * b.std_error;
+        let str = format!("interval widths at {}", level);
+        gsl::test(pu - pl > 2.0 * half || bu - bl > 2.0 * half || cu - cl > 2.0 * half, &str);
+    }
+
+    {
+        let mut rng = Xoshiro256StarStar::new(1);
+        let again = bootstrap(slice_a, mean, 4000, &mut rng);
+        gsl::test(again != b, "bootstrap reproducible with the same seed");
+
+        let mut rng = Xoshiro256StarStar::new(2);
+        let other = bootstrap(slice_a, mean, 4000, &mut rng);
+        gsl::test(other == b, "bootstrap differs with another seed");
+    }
+
+    {
+        let mut rng = Xoshiro256StarStar::new(3);
+        let b = bootstrap(slice_a,
+                          |x: &[f64]| {
+                              let mut sorted = x.to_vec();
+                              sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                              median_from_sorted_data(&sorted)
+                          },
+                          1000,
+                          &mut rng);
+        gsl::test_rel(b.estimate, 0.07505, 1.0e-10, "bootstrap median estimate");
+        let (lower, upper) = b.bca_interval(0.9);
+        gsl::test(lower > upper, "bootstrap median bca interval");
+    }
+}
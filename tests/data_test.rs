@@ -0,0 +1,102 @@
+// data_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::data::*;
+use stat::summary::describe;
+
+#[test]
+fn test_strided_slice() {
+    let slice_a = [0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                   0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+    let slice_b = [0.1081, 0.0986, 0.1566, 0.1961, 0.1125, 0.1942, 0.1079, 0.1021, 0.1583,
+                   0.1673, 0.1675, 0.1856, 0.1688, 0.1512];
+    let w = [1.0, 2.0, 3.0, 4.0, 0.0, 1.0, 2.0, 3.0, 4.0, 0.0, 1.0, 2.0, 3.0, 4.0];
+
+    // three interleaved channels
+    let mut buffer = Vec::new();
+    for i in 0..14 {
+        buffer.push(slice_a[i]);
+        buffer.push(slice_b[i]);
+        buffer.push(w[i]);
+    }
+
+    let a = StridedSlice::channel(&buffer, 3, 0);
+    let b = StridedSlice::new(&buffer[1..], 3);
+    let wa = StridedSlice::channel(&buffer, 3, 2);
+
+    gsl::test(a.len() != 14 || b.len() != 14 || wa.len() != 14, "strided len");
+    gsl::test(a.stride() != 3, "strided stride");
+    gsl::test(a[13] != 0.0479 || b[0] != 0.1081, "strided index");
+    gsl::test(b.iter().cloned().collect::<Vec<f64>>() != slice_b.to_vec(), "strided iter");
+
+    // the functions give the same results as on the copied data
+    gsl::test(mean(&a) != mean(&slice_a), "strided mean");
+    gsl::test(variance(&b) != variance(&slice_b), "strided variance");
+    gsl::test(sd(&a) != sd(&slice_a), "strided sd");
+    gsl::test(absdev(&a) != absdev(&slice_a), "strided absdev");
+    gsl::test(skew(&a) != skew(&slice_a), "strided skew");
+    gsl::test(kurtosis(&b) != kurtosis(&slice_b), "strided kurtosis");
+    gsl::test(lag1autocorrelation(&a) != lag1autocorrelation(&slice_a), "strided lag1");
+    gsl::test(covariance(&a, &b) != covariance(&slice_a, &slice_b), "strided covariance");
+    gsl::test(correlation(&a, &b) != correlation(&slice_a, &slice_b), "strided correlation");
+    gsl::test(t_test(&a, &b) != t_test(&slice_a, &slice_b), "strided t_test");
+    gsl::test(minmax(&b) != minmax(&slice_b), "strided minmax");
+    gsl::test(w_mean(&wa, &a) != w_mean(&w, &slice_a), "strided w_mean");
+    gsl::test(w_sd(&wa, &a) != w_sd(&w, &slice_a), "strided w_sd");
+    gsl::test(describe(&b) != describe(&slice_b), "strided describe");
+
+    // strided and contiguous data mix
+    gsl::test(covariance(&a, &slice_b) != covariance(&slice_a, &slice_b),
+              "strided covariance mixed");
+
+    // like the arguments (data, stride, n) of the GSL functions
+    let first = StridedSlice::with_len(&buffer, 3, 5);
+    gsl::test(first.len() != 5, "strided with_len");
+    gsl::test_rel(mean(&first), mean(&slice_a[..5]), 1.0e-15, "strided with_len mean");
+
+    let sorted = [1, 5, 2, 6, 3, 7, 4, 8];
+    let lower = StridedSlice::new(&sorted, 2);
+    gsl::test(median_from_sorted_data(&lower) != 2.5, "strided median");
+    gsl::test(quantile_from_sorted_data(&lower, 0.5) != 2.5, "strided quantile");
+    gsl::test(mad0(&lower) != 1.0, "strided mad0");
+
+    // a stride of one is the slice itself
+    let all = StridedSlice::new(&slice_a, 1);
+    gsl::test(mean(&all) != mean(&slice_a), "strided stride one");
+
+    let empty = StridedSlice::new(&buffer[..0], 4);
+    gsl::test(!empty.is_empty() || empty.iter().next().is_some(), "strided empty");
+}
+
+#[test]
+fn test_data_types() {
+    let v = vec![1u8, 2, 3, 4];
+    let s: &[u8] = &v;
+
+    // arrays, vectors, slices and references to them are all accepted
+    gsl::test(mean(&[1u8, 2, 3, 4]) != 2.5, "array mean");
+    gsl::test(mean(&v) != 2.5, "vec mean");
+    gsl::test(mean(s) != 2.5, "slice mean");
+    gsl::test(mean(&s) != 2.5, "slice reference mean");
+    gsl::test(variance(&&v) != variance(s), "vec reference variance");
+}
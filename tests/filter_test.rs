@@ -0,0 +1,83 @@
+// filter_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::filter::*;
+
+const SPIKES: [f64; 11] = [1.0, 1.2, 9.0, 1.1, 0.9, 1.3, -7.0, 1.0, 1.2, 1.1, 5.0];
+
+fn test_series(result: &[f64], expected: &[f64], desc: &str) {
+    gsl::test(result.len() != expected.len(), desc);
+    for (&r, &e) in result.iter().zip(expected) {
+        gsl::test_rel(r, e, 1.0e-12, desc);
+    }
+}
+
+#[test]
+fn test_median_filter() {
+    test_series(&median_filter(&SPIKES, 2, EndType::PadZero),
+                &[1.0, 1.1, 1.1, 1.2, 1.1, 1.0, 1.0, 1.1, 1.1, 1.1, 1.1],
+                "median_filter pad zero");
+    test_series(&median_filter(&SPIKES, 2, EndType::PadValue),
+                &[1.0, 1.1, 1.1, 1.2, 1.1, 1.0, 1.0, 1.1, 1.1, 1.2, 5.0],
+                "median_filter pad value");
+    test_series(&median_filter(&SPIKES, 2, EndType::Truncate),
+                &[1.2, 1.15, 1.1, 1.2, 1.1, 1.0, 1.0, 1.1, 1.1, 1.15, 1.2],
+                "median_filter truncate");
+
+    test_series(&median_filter(&SPIKES, 0, EndType::PadZero), &SPIKES, "median_filter window 1");
+    test_series(&median_filter(&[3, 1, 2], 5, EndType::Truncate),
+                &[2.0, 2.0, 2.0],
+                "median_filter wide window");
+    gsl::test(!median_filter::<f64, _>(&[], 2, EndType::PadValue).is_empty(),
+              "median_filter empty");
+}
+
+#[test]
+fn test_hampel_filter() {
+    let h = hampel_filter(&SPIKES, 2, 3.0);
+
+    test_series(&h.filtered,
+                &[1.0, 1.2, 1.1, 1.1, 0.9, 1.3, 1.0, 1.0, 1.2, 1.1, 1.2],
+                "hampel_filter filtered");
+    test_series(&h.median,
+                &median_filter(&SPIKES, 2, EndType::Truncate),
+                "hampel_filter median");
+    gsl::test_rel(h.scale[0], 0.29652044370112035, 1.0e-12, "hampel_filter scale");
+    gsl::test_rel(h.scale[1], 0.14826022185056018, 1.0e-12, "hampel_filter scale");
+
+    let indices: Vec<usize> = (0..SPIKES.len()).filter(|&i| h.outliers[i]).collect();
+    gsl::test(indices != vec![2, 6, 10], "hampel_filter outliers");
+    gsl::test(h.n_outliers() != 3, "hampel_filter n_outliers");
+
+    // the cleaned data is ready for the usual statistics
+    gsl::test(sd(&h.filtered) > 0.2, "hampel_filter sd");
+
+    // a large n_sigma keeps everything
+    let h = hampel_filter(&SPIKES, 2, 100.0);
+    gsl::test(h.n_outliers() != 0, "hampel_filter n_sigma");
+    test_series(&h.filtered, &SPIKES, "hampel_filter unchanged");
+
+    // a constant window has zero scale, so any deviation is an outlier
+    let h = hampel_filter(&[4, 4, 4, 5, 4, 4, 4], 3, 3.0);
+    gsl::test(!h.outliers[3] || h.filtered[3] != 4.0, "hampel_filter zero scale");
+}
@@ -0,0 +1,100 @@
+// float_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+
+use stat::data::StridedSlice;
+use stat::float;
+
+#[test]
+fn test_float() {
+    let groupa: [f32; 14] = [0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815,
+                             0.1186, 0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+    let rel = 1e-5;
+
+    let mean: f32 = float::mean(&groupa);
+    gsl::test_rel(mean as f64, 0.0728, rel, "float mean");
+
+    let var: f32 = float::variance(&groupa);
+    gsl::test_rel(var as f64, 1.22594153846154e-03, rel, "float variance");
+
+    let sd: f32 = float::sd(&groupa);
+    gsl::test_rel(sd as f64, 3.50134479659107e-02, rel, "float sd");
+
+    let var_m: f32 = float::variance_with_fixed_mean(&groupa, 0.0729);
+    gsl::test_rel(var_m as f64, 1.13838428571429e-03, rel,
+                  "float variance_with_fixed_mean");
+
+    let tss: f32 = float::tss(&groupa);
+    gsl::test_rel(tss as f64, 1.593724e-02, rel, "float tss");
+
+    let absdev: f32 = float::absdev(&groupa);
+    gsl::test_rel(absdev as f64, 2.87571428571429e-02, rel, "float absdev");
+
+    let skew: f32 = float::skew(&groupa);
+    gsl::test_rel(skew as f64, 0.0954642051479004, 1e-4, "float skew");
+
+    let kurt: f32 = float::kurtosis(&groupa);
+    gsl::test_rel(kurt as f64, -1.38583851548909, rel, "float kurtosis");
+
+    let (max, max_index) = float::max(&groupa);
+    gsl::test(max != 0.1331 || max_index != 4, "float max");
+
+    let (min, min_index) = float::min(&groupa);
+    gsl::test(min != 0.0242 || min_index != 3, "float min");
+
+    let left = StridedSlice::new(&groupa, 2);
+    let mean: f32 = float::mean(&left);
+    gsl::test_rel(mean as f64, stat::mean(&left), rel, "float strided mean");
+}
+
+#[test]
+fn test_float_acc() {
+    // a large offset leaves few bits of the f32 deviations
+    let data: Vec<f32> = (0..1000).map(|i| 10000.0 + (i % 7) as f32 * 0.01).collect();
+
+    let mean: f32 = float::mean_acc::<f64, _, _>(&data);
+    gsl::test(mean != stat::mean(&data) as f32, "float mean_acc");
+
+    let var: f32 = float::variance_acc::<f64, _, _>(&data);
+    gsl::test(var != stat::variance(&data) as f32, "float variance_acc");
+
+    let sd: f32 = float::sd_acc::<f64, _, _>(&data);
+    gsl::test(sd != stat::sd(&data) as f32, "float sd_acc");
+
+    let tss: f32 = float::tss_acc::<f64, _, _>(&data);
+    gsl::test(tss != stat::tss(&data) as f32, "float tss_acc");
+
+    let absdev: f32 = float::absdev_acc::<f64, _, _>(&data);
+    gsl::test(absdev != stat::absdev(&data) as f32, "float absdev_acc");
+
+    let skew: f32 = float::skew_acc::<f64, _, _>(&data);
+    gsl::test(skew != stat::skew(&data) as f32, "float skew_acc");
+
+    let kurtosis: f32 = float::kurtosis_acc::<f64, _, _>(&data);
+    gsl::test(kurtosis != stat::kurtosis(&data) as f32, "float kurtosis_acc");
+
+    let var_f32: f32 = float::variance(&data);
+    gsl::test_rel(var_f32 as f64, var as f64, 0.5, "float variance in f32");
+
+    let mean: f64 = float::mean(&[1.0, 2.0, 4.0]);
+    gsl::test_rel(mean, 7.0 / 3.0, 1e-15, "float f64 mean");
+}
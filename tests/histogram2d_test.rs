@@ -0,0 +1,125 @@
+// histogram2d_test.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::histogram::HistogramError;
+use stat::histogram2d::{Histogram2d, Histogram2dPdf};
+
+#[test]
+fn test_histogram2d() {
+    let rel = 1.0e-10;
+
+    let mut h = Histogram2d::new_uniform(3, 2, 0.0, 3.0, 0.0, 2.0);
+
+    for &(x, y) in &[(0.5, 0.5), (0.5, 0.5), (1.5, 0.5), (1.5, 1.5), (1.5, 1.5), (1.5, 1.5),
+                     (2.5, 1.5)] {
+        gsl::test(h.increment(x, y).is_err(), "increment in range");
+    }
+
+    gsl::test(h.increment(3.0, 0.5) != Err(HistogramError::OutOfRange),
+              "increment outside x range");
+    gsl::test(h.increment(0.5, -0.5) != Err(HistogramError::OutOfRange),
+              "increment outside y range");
+
+    {
+        let expected = [2.0, 0.0, 1.0, 3.0, 0.0, 1.0];
+        let str = format!("bins ({:?} observed vs {:?} expected)", h.values(), expected);
+        gsl::test(h.values() != &expected[..], &str);
+    }
+
+    {
+        gsl::test(h.find(1.2, 1.7) != Some((1, 1)), "find");
+        gsl::test(h.max_bin() != (1, 1), "max_bin");
+        gsl::test(h.min_bin() != (0, 1), "min_bin");
+        gsl::test_rel(h.max_val(), 3.0, rel, "max_val");
+        gsl::test_rel(h.min_val(), 0.0, rel, "min_val");
+        gsl::test_rel(h.sum(), 7.0, rel, "sum");
+    }
+
+    {
+        gsl::test_rel(h.xmean(), 1.3571428571428572, rel, "xmean");
+        gsl::test_rel(h.ymean(), 1.0714285714285714, rel, "ymean");
+        gsl::test_rel(h.xsigma(), 0.6388765649999399, rel, "xsigma");
+        gsl::test_rel(h.ysigma(), 0.4948716593053935, rel, "ysigma");
+        gsl::test_rel(h.cov(), 0.22448979591836732, rel, "cov");
+    }
+
+    {
+        let px = h.x_projection();
+        let py = h.y_projection();
+        gsl::test(px.values() != &[2.0, 4.0, 1.0][..], "x_projection");
+        gsl::test(py.values() != &[3.0, 4.0][..], "y_projection");
+        gsl::test_rel(px.mean(), h.xmean(), rel, "x_projection mean");
+        gsl::test_rel(py.sigma(), h.ysigma(), rel, "y_projection sigma");
+    }
+
+    {
+        let mut h2 = h.clone();
+        h2.add(&h).unwrap();
+        gsl::test_rel(h2.get(1, 1), 6.0, rel, "add");
+        h2.scale(0.5);
+        gsl::test_rel(h2.get(1, 1), 3.0, rel, "scale");
+
+        let other = Histogram2d::new_uniform(3, 3, 0.0, 3.0, 0.0, 2.0);
+        gsl::test(h2.sub(&other) != Err(HistogramError::Mismatch), "sub mismatch");
+    }
+}
+
+#[test]
+fn test_histogram2d_pdf() {
+    let rel = 1.0e-10;
+
+    let mut h = Histogram2d::with_ranges(&[0.0, 1.0, 2.0, 3.0], &[0.0, 1.0, 2.0]);
+    for &(x, y) in &[(0.5, 0.5), (0.5, 0.5), (1.5, 0.5), (1.5, 1.5), (1.5, 1.5), (1.5, 1.5),
+                     (2.5, 1.5)] {
+        h.increment(x, y).unwrap();
+    }
+
+    let p = Histogram2dPdf::new(&h).unwrap();
+
+    {
+        let (x, y) = p.sample(0.5, 0.25);
+        gsl::test_rel(x, 1.1666666666666667, rel, "sample x");
+        gsl::test_rel(y, 1.25, rel, "sample y");
+    }
+
+    {
+        let (x, y) = p.sample(0.0, 0.0);
+        gsl::test_rel(x, 0.0, rel, "sample lower edge x");
+        gsl::test_rel(y, 0.0, rel, "sample lower edge y");
+    }
+
+    {
+        let (x, y) = p.sample(1.0, 1.0);
+        gsl::test_rel(x, 0.0, rel, "sample wraps upper edge x");
+        gsl::test_rel(y, 0.0, rel, "sample wraps upper edge y");
+    }
+
+    {
+        h.accumulate(0.5, 1.5, -1.0).unwrap();
+        gsl::test(Histogram2dPdf::new(&h) != Err(HistogramError::NegativeBin),
+                  "pdf of negative bins");
+        h.reset();
+        gsl::test(Histogram2dPdf::new(&h) != Err(HistogramError::Empty),
+                  "pdf of empty histogram");
+    }
+}
@@ -0,0 +1,233 @@
+// histogram_test.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::histogram::{bin_count, bin_width, BinRule, Histogram, HistogramError, HistogramPdf,
+                      WIDTH_MAX_BINS};
+
+#[test]
+fn test_histogram() {
+    let rel = 1.0e-10;
+
+    let mut h = Histogram::new_uniform(5, 0.0, 10.0);
+
+    for &x in &[1.0, 3.0, 3.0, 5.0, 7.0, 9.99] {
+        gsl::test(h.increment(x).is_err(), "increment in range");
+    }
+
+    gsl::test(h.increment(10.0) != Err(HistogramError::OutOfRange),
+              "increment at upper edge");
+    gsl::test(h.increment(-1.0) != Err(HistogramError::OutOfRange),
+              "increment below lower edge");
+
+    {
+        let expected = [1.0, 2.0, 1.0, 1.0, 1.0];
+        let str = format!("bins ({:?} observed vs {:?} expected)", h.values(), expected);
+        gsl::test(h.values() != &expected[..], &str);
+    }
+
+    {
+        let (lower, upper) = h.bin_range(2);
+        gsl::test_rel(lower, 4.0, rel, "bin_range lower");
+        gsl::test_rel(upper, 6.0, rel, "bin_range upper");
+        gsl::test_rel(h.min(), 0.0, rel, "min");
+        gsl::test_rel(h.max(), 10.0, rel, "max");
+    }
+
+    {
+        gsl::test(h.find(5.5) != Some(2), "find");
+        gsl::test(h.find(10.0).is_some(), "find upper edge");
+        gsl::test(h.max_bin() != 1, "max_bin");
+        gsl::test(h.min_bin() != 0, "min_bin");
+        gsl::test_rel(h.max_val(), 2.0, rel, "max_val");
+        gsl::test_rel(h.min_val(), 1.0, rel, "min_val");
+    }
+
+    {
+        gsl::test_rel(h.sum(), 6.0, rel, "sum");
+        gsl::test_rel(h.mean(), 4.666666666666667, rel, "mean");
+        gsl::test_rel(h.sigma(), 2.6874192494328497, rel, "sigma");
+    }
+
+    {
+        let mut h2 = h.clone();
+        h2.add(&h).unwrap();
+        gsl::test_rel(h2.get(1), 4.0, rel, "add");
+        h2.sub(&h).unwrap();
+        gsl::test_rel(h2.get(1), 2.0, rel, "sub");
+        h2.scale(0.5);
+        gsl::test_rel(h2.get(1), 1.0, rel, "scale");
+        h2.shift(1.0);
+        gsl::test_rel(h2.get(1), 2.0, rel, "shift");
+
+        let other = Histogram::new_uniform(4, 0.0, 10.0);
+        gsl::test(h2.add(&other) != Err(HistogramError::Mismatch), "add mismatch");
+    }
+}
+
+#[test]
+fn test_histogram_ranges() {
+    let rel = 1.0e-10;
+
+    let mut h = Histogram::with_ranges(&[0.0, 1.0, 10.0, 100.0, 1000.0]);
+
+    gsl::test(h.bins() != 4, "bins");
+
+    for &x in &[0.5, 2.0, 50.0, 99.9, 100.0, 999.0] {
+        h.accumulate(x, 2.0).unwrap();
+    }
+
+    {
+        let expected = [2.0, 2.0, 4.0, 4.0];
+        let str = format!("bins ({:?} observed vs {:?} expected)", h.values(), expected);
+        gsl::test(h.values() != &expected[..], &str);
+    }
+
+    gsl::test(h.find(0.999) != Some(0), "find first bin");
+    gsl::test(h.find(100.0) != Some(3), "find bin edge");
+    gsl::test_rel(h.sum(), 12.0, rel, "sum");
+
+    h.reset();
+    gsl::test_rel(h.sum(), 0.0, rel, "reset");
+}
+
+#[test]
+fn test_bin_rules() {
+    let rel = 1.0e-10;
+
+    let uniform: Vec<i32> = (1..101).collect();
+    let exponential: Vec<f64> = (0..200).map(|i| -(1.0 - (i as f64 + 0.5) / 200.0).ln()).collect();
+    let bimodal: Vec<f64> = (0..100)
+        .map(|i| if i < 50 { i as f64 / 50.0 } else { 10.0 + (i - 50) as f64 / 50.0 })
+        .collect();
+
+    let rules = [BinRule::Sturges,
+                 BinRule::Sqrt,
+                 BinRule::Rice,
+                 BinRule::Scott,
+                 BinRule::FreedmanDiaconis,
+                 BinRule::Doane,
+                 BinRule::Knuth];
+
+    let expected_uniform = [8, 10, 10, 5, 5, 8, 1];
+    let expected_exponential = [9, 15, 12, 11, 17, 13, 8];
+    let expected_bimodal = [8, 10, 10, 3, 3, 8, 11];
+
+    for (i, &rule) in rules.iter().enumerate() {
+        let k = bin_count(&uniform, rule);
+        let str = format!("{:?} uniform ({} observed vs {} expected)", rule, k, expected_uniform[i]);
+        gsl::test(k != expected_uniform[i], &str);
+
+        let k = bin_count(&exponential, rule);
+        let str = format!("{:?} exponential ({} observed vs {} expected)",
+                          rule,
+                          k,
+                          expected_exponential[i]);
+        gsl::test(k != expected_exponential[i], &str);
+
+        let k = bin_count(&bimodal, rule);
+        let str = format!("{:?} bimodal ({} observed vs {} expected)", rule, k, expected_bimodal[i]);
+        gsl::test(k != expected_bimodal[i], &str);
+    }
+
+    {
+        let width = bin_width(&uniform, BinRule::Sqrt);
+        gsl::test_rel(width, 9.9, rel, "bin_width");
+    }
+
+    {
+        let h = Histogram::from_data(&uniform, BinRule::Sqrt);
+        gsl::test(h.bins() != 10, "from_data bins");
+        gsl::test_rel(h.sum(), 100.0, rel, "from_data sum");
+        gsl::test_rel(h.get(9), 10.0, rel, "from_data includes maximum");
+    }
+}
+
+#[test]
+fn test_bin_rules_heavy_tail() {
+    // the iqr of 1 asks for about 1e300 bins to reach the outlier
+    let data = [0.0, 0.0, 0.0, 1.0, 1e300];
+
+    let k = bin_count(&data, BinRule::FreedmanDiaconis);
+    gsl::test(k != WIDTH_MAX_BINS, "FreedmanDiaconis heavy tail capped");
+
+    let cauchy: Vec<f64> = (1..200)
+        .map(|i| (std::f64::consts::PI * (i as f64 / 200.0 - 0.5)).tan())
+        .chain(Some(1e200))
+        .collect();
+    let k = bin_count(&cauchy, BinRule::FreedmanDiaconis);
+    gsl::test(k != WIDTH_MAX_BINS, "FreedmanDiaconis cauchy capped");
+
+    let h = Histogram::from_data(&data, BinRule::FreedmanDiaconis);
+    gsl::test(h.bins() != WIDTH_MAX_BINS, "from_data heavy tail bins");
+    gsl::test(h.sum() != 5.0, "from_data heavy tail sum");
+}
+
+#[test]
+#[should_panic(expected = "data must not be empty")]
+fn test_from_empty_data() {
+    let empty: [f64; 0] = [];
+    Histogram::from_data(&empty, BinRule::Sqrt);
+}
+
+#[test]
+fn test_histogram_pdf() {
+    let rel = 1.0e-10;
+
+    let mut h = Histogram::with_ranges(&[0.0, 1.0, 2.0, 4.0]);
+    h.accumulate(0.5, 1.0).unwrap();
+    h.accumulate(3.0, 3.0).unwrap();
+
+    let p = HistogramPdf::new(&h).unwrap();
+
+    {
+        let expected = [0.0, 0.25, 0.25, 1.0];
+        let str = format!("sum ({:?} observed vs {:?} expected)", p.sum(), expected);
+        gsl::test(p.sum() != &expected[..], &str);
+    }
+
+    {
+        gsl::test_rel(p.sample(0.1), 0.4, rel, "sample in first bin");
+        gsl::test_rel(p.sample(0.25), 2.0, rel, "sample skips empty bin");
+        gsl::test_rel(p.sample(0.625), 3.0, rel, "sample in last bin");
+        gsl::test_rel(p.sample(1.0), 0.0, rel, "sample wraps upper edge");
+    }
+
+    {
+        gsl::test_rel(p.cdf(-1.0), 0.0, rel, "cdf below range");
+        gsl::test_rel(p.cdf(0.4), 0.1, rel, "cdf in first bin");
+        gsl::test_rel(p.cdf(1.5), 0.25, rel, "cdf in empty bin");
+        gsl::test_rel(p.cdf(3.0), 0.625, rel, "cdf in last bin");
+        gsl::test_rel(p.cdf(4.0), 1.0, rel, "cdf above range");
+    }
+
+    for i in 0..100 {
+        let u = i as f64 / 100.0;
+        gsl::test_rel(p.cdf(p.sample(u)), u, rel, "cdf inverts sample");
+    }
+
+    {
+        h.shift(-2.0);
+        gsl::test(HistogramPdf::new(&h) != Err(HistogramError::NegativeBin),
+                  "pdf of negative bins");
+    }
+}
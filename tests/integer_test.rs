@@ -0,0 +1,86 @@
+// integer_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+
+use stat::integer;
+
+#[test]
+fn test_integer() {
+    let counters: [u64; 4] = [u64::MAX, u64::MAX - 1, u64::MAX - 2, u64::MAX - 6];
+
+    let sum = integer::sum(&counters);
+    gsl::test(sum != Some(73786976294838206451), "integer sum");
+
+    let mean = integer::mean(&counters);
+    gsl::test(mean != Some(18446744073709551616.0), "integer mean");
+
+    let tss = integer::tss(&counters);
+    gsl::test(tss != Some(20.75), "integer tss");
+
+    let var = integer::variance(&counters).unwrap();
+    gsl::test_rel(var, 6.916666666666667, 1e-15, "integer variance");
+
+    let sd = integer::sd(&counters).unwrap();
+    gsl::test_rel(sd, 6.916666666666667f64.sqrt(), 1e-15, "integer sd");
+
+    // the floating point path loses the deviations
+    gsl::test(stat::tss(&counters) == 20.75, "integer tss against floating point");
+}
+
+#[test]
+fn test_integer_rounding() {
+    // 2^53 + 4/3 rounds up to 2^53 + 2
+    let data: [i64; 3] = [(1 << 53) + 1, (1 << 53) + 1, (1 << 53) + 2];
+    let mean = integer::mean(&data);
+    gsl::test(mean != Some(9007199254740994.0), "integer mean rounding");
+
+    // 2^53 + 1 is a tie, rounded to even
+    let mean = integer::mean(&[(1i64 << 53) + 1]);
+    gsl::test(mean != Some(9007199254740992.0), "integer mean tie");
+
+    let data: [i64; 3] = [-(1 << 62) - 3, -(1 << 62) - 1, -(1 << 62)];
+    let mean = integer::mean(&data);
+    gsl::test(mean != Some(-4.611686018427388e+18), "integer negative mean");
+
+    let tss = integer::tss(&data).unwrap();
+    gsl::test_rel(tss, 4.666666666666667, 1e-15, "integer negative tss");
+
+    let data = [3u8, 1, 4, 1, 5, 9, 2, 6];
+    gsl::test_rel(integer::mean(&data).unwrap(), stat::mean(&data), 1e-15,
+                  "integer mean of bytes");
+    gsl::test_rel(integer::variance(&data).unwrap(), stat::variance(&data), 1e-15,
+                  "integer variance of bytes");
+}
+
+#[test]
+fn test_integer_overflow() {
+    gsl::test(integer::sum(&[i128::MAX, 1]).is_some(), "integer sum overflow");
+    gsl::test(integer::mean(&[i128::MAX, 1]).is_some(), "integer mean overflow");
+    gsl::test(integer::tss(&[i128::MIN, i128::MAX]).is_some(), "integer tss overflow");
+    let tss = integer::tss(&[-(1i64 << 60), 1 << 60]);
+    gsl::test(tss != Some(2f64.powi(121)), "integer tss of wide i64");
+
+    let empty: [u32; 0] = [];
+    gsl::test(integer::sum(&empty) != Some(0), "integer sum of nothing");
+    gsl::test(!integer::mean(&empty).unwrap().is_nan(), "integer mean of nothing");
+    gsl::test(integer::tss(&empty) != Some(0.0), "integer tss of nothing");
+}
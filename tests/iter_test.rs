@@ -0,0 +1,87 @@
+// iter_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use std::collections::{BTreeMap, VecDeque};
+
+use stat::iter::*;
+
+#[test]
+fn test_iter() {
+    let slice_a = [0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                   0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+
+    let rel = 1.0e-10;
+
+    let deque: VecDeque<f64> = slice_a.iter().cloned().collect();
+    gsl::test_rel(deque.iter().cloned().mean(), stat::mean(&slice_a), rel, "iter mean");
+    gsl::test_rel(deque.iter().cloned().variance(),
+                  stat::variance(&slice_a),
+                  rel,
+                  "iter variance");
+    gsl::test_rel(deque.iter().cloned().sd(), stat::sd(&slice_a), rel, "iter sd");
+    gsl::test_rel(deque.iter().cloned().skew(), stat::skew(&slice_a), rel, "iter skew");
+    gsl::test_rel(deque.iter().cloned().kurtosis(),
+                  stat::kurtosis(&slice_a),
+                  rel,
+                  "iter kurtosis");
+
+    let (min, i, max, j) = deque.into_iter().minmax().unwrap();
+    gsl::test(min != 0.0242 || i != 3 || max != 0.1331 || j != 4, "iter minmax");
+
+    // the values of a map, lazily converted
+    let map: BTreeMap<&str, i32> = [("a", 17), ("b", 18), ("c", 16), ("d", 18), ("e", 12)]
+        .iter()
+        .cloned()
+        .collect();
+    let s = map.values().cloned().stats();
+    gsl::test(s.len() != 5, "iter stats len");
+    gsl::test_rel(s.mean(), 16.2, rel, "iter stats mean");
+    gsl::test_rel(s.variance(), 6.2, rel, "iter stats variance");
+    gsl::test(s.min() != Some((12.0, 4)) || s.max() != Some((18.0, 1)), "iter stats extremes");
+
+    let parsed = "1.5 2.5 4.0".split(' ').map(|t| t.parse::<f64>().unwrap());
+    gsl::test_rel(parsed.mean(), 8.0 / 3.0, rel, "iter parsed mean");
+}
+
+#[test]
+fn test_running_stats() {
+    let mut s = RunningStats::new();
+    gsl::test(!s.is_empty() || s.min().is_some() || s.max().is_some(), "running empty");
+    gsl::test(s.mean() != 0.0, "running empty mean");
+
+    // a large offset doesn't hurt the one-pass variance
+    for &x in &[4.0, 7.0, 13.0, 16.0] {
+        s.add(1.0e9 + x);
+    }
+    gsl::test_rel(s.mean(), 1.0e9 + 10.0, 1.0e-15, "running mean");
+    gsl::test_rel(s.variance(), 30.0, 1.0e-9, "running variance");
+
+    let mut s = RunningStats::new();
+    s.add(3u8);
+    gsl::test(!s.variance().is_nan(), "running single value variance");
+
+    // the first NaN is kept, like min and max
+    let (min, i, max, j) = [1.0, f64::NAN, -1.0, f64::NAN].iter().cloned().minmax().unwrap();
+    gsl::test(!min.is_nan() || i != 1 || !max.is_nan() || j != 1, "running nan");
+
+    gsl::test(Vec::<f64>::new().into_iter().minmax().is_some(), "iter minmax empty");
+}
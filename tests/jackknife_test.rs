@@ -0,0 +1,92 @@
+// jackknife_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::jackknife::*;
+
+#[test]
+fn test_jackknife() {
+    let slice_a = &[0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                    0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+
+    let rel = 1.0e-10;
+
+    {
+        let j = jackknife(slice_a, mean);
+        gsl::test_rel(j.estimate, 0.0728, rel, "jackknife mean estimate");
+        gsl::test(j.bias.abs() > 1.0e-15, "jackknife mean bias");
+        gsl::test_rel(j.corrected, 0.0728, rel, "jackknife mean corrected");
+        gsl::test_rel(j.std_error, 0.009357737587005355, rel, "jackknife mean std_error");
+
+        // the standard error of the mean is sd / sqrt(n)
+        let expected = sd(slice_a) / (slice_a.len() as f64).sqrt();
+        gsl::test_rel(j.std_error, expected, rel, "jackknife mean std_error");
+    }
+
+    {
+        let j = jackknife(slice_a, variance);
+        gsl::test_rel(j.estimate, 0.0012259415384615383, rel, "jackknife variance estimate");
+        gsl::test_rel(j.std_error, 0.00034397723918191676, rel, "jackknife variance std_error");
+    }
+
+    {
+        // the jackknife corrects the bias of the variance about the mean
+        let j = jackknife(slice_a, |x: &[f64]| variance_with_fixed_mean(x, mean(x)));
+        gsl::test_rel(j.estimate, 0.0011383742857142855, rel, "jackknife biased estimate");
+        gsl::test_rel(j.bias, -8.756725274724846e-05, 1.0e-8, "jackknife biased bias");
+        gsl::test_rel(j.corrected, variance(slice_a), rel, "jackknife biased corrected");
+        gsl::test_rel(j.std_error, 0.00031751745155253876, rel, "jackknife biased std_error");
+    }
+
+    {
+        let fast = jackknife_mean(slice_a);
+        let slow = jackknife(slice_a, mean);
+        gsl::test_rel(fast.estimate, slow.estimate, rel, "jackknife_mean estimate");
+        gsl::test_rel(fast.std_error, slow.std_error, rel, "jackknife_mean std_error");
+        for i in 0..slice_a.len() {
+            gsl::test_rel(fast.leave_one_out[i],
+                          slow.leave_one_out[i],
+                          rel,
+                          "jackknife_mean leave_one_out");
+        }
+    }
+
+    {
+        let fast = jackknife_variance(slice_a);
+        let slow = jackknife(slice_a, variance);
+        gsl::test_rel(fast.estimate, slow.estimate, rel, "jackknife_variance estimate");
+        gsl::test_rel(fast.std_error, slow.std_error, 1.0e-8, "jackknife_variance std_error");
+        for i in 0..slice_a.len() {
+            gsl::test_rel(fast.leave_one_out[i],
+                          slow.leave_one_out[i],
+                          rel,
+                          "jackknife_variance leave_one_out");
+        }
+    }
+
+    {
+        let ints = &[17, 18, 16, 18, 12, 20, 18, 20, 20, 22];
+        let fast = jackknife_variance(ints);
+        let slow = jackknife(ints, variance);
+        gsl::test_rel(fast.std_error, slow.std_error, 1.0e-8, "jackknife_variance (int)");
+    }
+}
@@ -0,0 +1,105 @@
+// kde_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::kde::*;
+
+// direct summation of the kernel density at x
+fn direct(data: &[f64], kernel: Kernel, bandwidth: f64, x: f64) -> f64 {
+    let sum: f64 = data.iter().map(|&xi| kernel.eval((x - xi) / bandwidth)).sum();
+    sum / (data.len() as f64 * bandwidth)
+}
+
+#[test]
+fn test_kde() {
+    // values lying on the grid points, so that the binning is exact
+    let data = &[2.0, 2.5, 3.0, 3.0, 4.2, 5.0, 5.1, 7.7];
+
+    let kernels = [Kernel::Gaussian, Kernel::Epanechnikov, Kernel::Triangular, Kernel::Uniform];
+
+    for &kernel in &kernels {
+        let est = kde_grid(data, kernel, 0.75, 0.0, 10.0, 101);
+
+        gsl::test(est.x.len() != 101 || est.density.len() != 101, "kde_grid length");
+
+        for i in 0..101 {
+            let expected = direct(data, kernel, 0.75, est.x[i]);
+            let str = format!("kde_grid {:?} at {} ({} observed vs {} expected)",
+                              kernel,
+                              est.x[i],
+                              est.density[i],
+                              expected);
+            gsl::test((est.density[i] - expected).abs() > 1.0e-10, &str);
+        }
+    }
+
+    for &kernel in &kernels {
+        let est = kde(data, kernel, 0.5);
+        let delta = est.x[1] - est.x[0];
+
+        // trapezoidal integration of the density
+        let n = est.density.len();
+        let integral = delta * (est.density.iter().sum::<f64>() -
+                                0.5 * (est.density[0] + est.density[n - 1]));
+        let str = format!("kde {:?} integral", kernel);
+        gsl::test_rel(integral, 1.0, 1.0e-2, &str);
+    }
+}
+
+#[test]
+fn test_bandwidth() {
+    let rel = 1.0e-10;
+
+    let uniform: Vec<i32> = (1..101).collect();
+
+    {
+        let bw = silverman_bandwidth(&uniform);
+        let expected = 10.39471468564849;
+        gsl::test_rel(bw, expected, rel, "silverman_bandwidth");
+    }
+
+    {
+        let bw = scott_bandwidth(&uniform);
+        let expected = 12.231114280113056;
+        gsl::test_rel(bw, expected, rel, "scott_bandwidth");
+    }
+
+    {
+        // the IQR keeps the outlier from inflating the bandwidth
+        let bw = silverman_bandwidth(&[1, 2, 2, 3, 3, 3, 4, 4, 5, 100]);
+        let expected = 0.7366625778771716;
+        gsl::test_rel(bw, expected, rel, "silverman_bandwidth (outlier)");
+    }
+
+    {
+        // constant data falls back to the size of the values, like bw.nrd0
+        let constant = [5.0; 10];
+        let bw = silverman_bandwidth(&constant);
+        gsl::test_rel(bw, 0.9 * 5.0 * 10f64.powf(-0.2), rel, "silverman_bandwidth (constant)");
+
+        let est = kde(&constant, Kernel::Gaussian, bw);
+        let peak = est.density.iter().cloned().fold(0.0, f64::max);
+        gsl::test(peak <= 0.0 || peak.is_nan(), "kde of constant data");
+
+        let bw = scott_bandwidth(&[0, 0, 0]);
+        gsl::test_rel(bw, 1.059 * 3f64.powf(-0.2), rel, "scott_bandwidth (zeros)");
+    }
+}
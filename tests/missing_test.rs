@@ -0,0 +1,168 @@
+// missing_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+
+use stat::missing;
+
+#[test]
+fn test_missing() {
+    let readings = [Some(0.0421), None, Some(0.0941), Some(0.1064), None, Some(0.0242),
+                    Some(0.1331), Some(0.0773)];
+    let values = [0.0421, 0.0, 0.0941, 0.1064, 0.0, 0.0242, 0.1331, 0.0773];
+    let valid = [true, false, true, true, false, true, true, true];
+    let clean = [0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773];
+
+    let mean = missing::mean(&readings);
+    gsl::test_rel(mean, stat::mean(&clean), 1e-15, "missing mean");
+    gsl::test(missing::mean((&values, &valid)) != mean, "missing mean of mask");
+    gsl::test(missing::mean(&readings.to_vec()) != mean, "missing mean of vec");
+    gsl::test(missing::mean(&readings[..]) != mean, "missing mean of slice");
+
+    let variance = missing::variance(&readings);
+    gsl::test_rel(variance, stat::variance(&clean), 1e-15, "missing variance");
+
+    let sd = missing::sd((&values, &valid));
+    gsl::test_rel(sd, stat::sd(&clean), 1e-15, "missing sd");
+
+    let tss = missing::tss(&readings);
+    gsl::test_rel(tss, stat::tss(&clean), 1e-15, "missing tss");
+
+    let absdev = missing::absdev(&readings);
+    gsl::test_rel(absdev, stat::absdev(&clean), 1e-15, "missing absdev");
+
+    let skew = missing::skew(&readings);
+    gsl::test_rel(skew, stat::skew(&clean), 1e-15, "missing skew");
+
+    let kurtosis = missing::kurtosis(&readings);
+    gsl::test_rel(kurtosis, stat::kurtosis(&clean), 1e-15, "missing kurtosis");
+
+    let median = missing::median(&readings);
+    gsl::test_rel(median, 0.0857, 1e-15, "missing median");
+
+    let q = missing::quantile((&values, &valid), 0.25);
+    gsl::test_rel(q, 0.0509, 1e-15, "missing quantile");
+
+    // the positions are those in the data with the missing values
+    gsl::test(missing::max(&readings) != (0.1331, 6), "missing max");
+    gsl::test(missing::min((&values, &valid)) != (0.0242, 5), "missing min");
+
+    let lag1 = missing::lag1autocorrelation(&readings);
+    gsl::test_rel(lag1, stat::lag1autocorrelation(&clean), 1e-15, "missing lag1autocorrelation");
+
+    let mad = missing::mad((&values, &valid));
+    gsl::test_rel(mad, stat::mad(&clean), 1e-15, "missing mad");
+
+    gsl::test(missing::values(&readings) != clean, "missing values");
+
+    let none: [Option<f64>; 2] = [None, None];
+    let max = missing::max(&none);
+    gsl::test(!max.0.is_nan() || max.1 != 0, "missing max of nothing");
+    gsl::test(!missing::median(&none).is_nan(), "missing median of nothing");
+
+    // a NaN present is not missing, and spoils the order like in max
+    let with_nan = [Some(1.0), Some(f64::NAN), None, Some(2.0)];
+    gsl::test(!missing::median(&with_nan).is_nan(), "missing median with NaN");
+    gsl::test(!missing::quantile(&with_nan, 0.25).is_nan(), "missing quantile with NaN");
+    gsl::test(!missing::mad(&with_nan).is_nan(), "missing mad with NaN");
+}
+
+#[test]
+fn test_missing_all() {
+    let none: [Option<f64>; 3] = [None, None, None];
+    let x = [1.0, 2.0, 3.0];
+
+    gsl::test(!missing::mean(&none).is_nan(), "missing mean of nothing");
+    gsl::test(!missing::variance(&none).is_nan(), "missing variance of nothing");
+    gsl::test(!missing::sd(&none).is_nan(), "missing sd of nothing");
+    gsl::test(!missing::tss(&none).is_nan(), "missing tss of nothing");
+    gsl::test(!missing::absdev(&none).is_nan(), "missing absdev of nothing");
+    gsl::test(!missing::skew(&none).is_nan(), "missing skew of nothing");
+    gsl::test(!missing::kurtosis(&none).is_nan(), "missing kurtosis of nothing");
+    gsl::test(!missing::lag1autocorrelation(&none).is_nan(),
+              "missing lag1autocorrelation of nothing");
+    gsl::test(!missing::mad(&none).is_nan(), "missing mad of nothing");
+
+    let min = missing::min(&none);
+    gsl::test(!min.0.is_nan() || min.1 != 0, "missing min of nothing");
+
+    let some = [Some(1.0), Some(2.0), Some(3.0)];
+    gsl::test(!missing::covariance(&some, &none).is_nan(), "missing covariance of nothing");
+    gsl::test(!missing::correlation(&none, &some).is_nan(), "missing correlation of nothing");
+    gsl::test(!missing::p_variance(&some, &none).is_nan(), "missing p_variance of nothing");
+    gsl::test(!missing::t_test(&none, &some).is_nan(), "missing t_test of nothing");
+    gsl::test(!missing::w_mean(&x, &none).is_nan(), "missing w_mean of nothing");
+    gsl::test(!missing::w_variance(&x, &none).is_nan(), "missing w_variance of nothing");
+    gsl::test(!missing::w_kurtosis(&x, &none).is_nan(), "missing w_kurtosis of nothing");
+}
+
+#[test]
+fn test_missing_pairs() {
+    let x = [Some(1.0), Some(2.0), None, Some(4.0), Some(5.0), Some(6.0)];
+    let y = [2.0, 4.5, 5.0, 7.0, 9.0, 13.0];
+    let y_valid = [true, true, true, false, true, true];
+    let (x_clean, y_clean) = ([1.0, 2.0, 5.0, 6.0], [2.0, 4.5, 9.0, 13.0]);
+
+    let cov = missing::covariance(&x, (&y, &y_valid));
+    gsl::test_rel(cov, stat::covariance(&x_clean, &y_clean), 1e-15, "missing covariance");
+
+    let r = missing::correlation(&x, (&y, &y_valid));
+    gsl::test_rel(r, stat::correlation(&x_clean, &y_clean), 1e-15, "missing correlation");
+
+    let (x_pairs, y_pairs) = missing::pairs(&x, (&y, &y_valid));
+    gsl::test(x_pairs != x_clean || y_pairs != y_clean, "missing pairs");
+
+    // the two samples are used separately
+    let y_present = [2.0, 4.5, 5.0, 9.0, 13.0];
+    let x_present = [1.0, 2.0, 4.0, 5.0, 6.0];
+    let pv = missing::p_variance(&x, (&y, &y_valid));
+    gsl::test_rel(pv, stat::p_variance(&x_present, &y_present), 1e-15, "missing p_variance");
+
+    let t = missing::t_test(&x, (&y, &y_valid));
+    gsl::test_rel(t, stat::t_test(&x_present, &y_present), 1e-15, "missing t_test");
+
+    let w = [1.0, 2.0, 2.0, 1.0, 3.0, 1.0];
+    let w_present = [1.0, 2.0, 1.0, 3.0, 1.0];
+
+    let wmean = missing::w_mean(&w, &x);
+    gsl::test_rel(wmean, stat::w_mean(&w_present, &x_present), 1e-15, "missing w_mean");
+
+    let wvariance = missing::w_variance(&w, &x);
+    gsl::test_rel(wvariance, stat::w_variance(&w_present, &x_present), 1e-15,
+                  "missing w_variance");
+
+    let wsd = missing::w_sd(&w, &x);
+    gsl::test_rel(wsd, stat::w_sd(&w_present, &x_present), 1e-15, "missing w_sd");
+
+    let wtss = missing::w_tss(&w, &x);
+    gsl::test_rel(wtss, stat::w_tss(&w_present, &x_present), 1e-15, "missing w_tss");
+
+    let wabsdev = missing::w_absdev(&w, &x);
+    gsl::test_rel(wabsdev, stat::w_absdev(&w_present, &x_present), 1e-15,
+                  "missing w_absdev");
+
+    let wskew = missing::w_skew(&w, &x);
+    gsl::test_rel(wskew, stat::w_skew(&w_present, &x_present), 1e-15, "missing w_skew");
+
+    let wkurtosis = missing::w_kurtosis(&w, &x);
+    gsl::test_rel(wkurtosis, stat::w_kurtosis(&w_present, &x_present), 1e-15,
+                  "missing w_kurtosis");
+}
@@ -0,0 +1,196 @@
+// nan_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+
+use stat::nan::{self, NanError, NanPolicy};
+
+#[test]
+fn test_nan_omit() {
+    let nan = f64::NAN;
+    let data = [0.0421, nan, 0.0941, 0.1064, nan, 0.0242, 0.1331, 0.0773];
+    let clean = [0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773];
+    let omit = NanPolicy::Omit;
+
+    let mean = nan::mean(&data, omit).unwrap();
+    gsl::test_rel(mean, stat::mean(&clean), 1e-15, "nan mean");
+
+    let variance = nan::variance(&data, omit).unwrap();
+    gsl::test_rel(variance, stat::variance(&clean), 1e-15, "nan variance");
+
+    let sd = nan::sd(&data, omit).unwrap();
+    gsl::test_rel(sd, stat::sd(&clean), 1e-15, "nan sd");
+
+    let tss = nan::tss(&data, omit).unwrap();
+    gsl::test_rel(tss, stat::tss(&clean), 1e-15, "nan tss");
+
+    let absdev = nan::absdev(&data, omit).unwrap();
+    gsl::test_rel(absdev, stat::absdev(&clean), 1e-15, "nan absdev");
+
+    let skew = nan::skew(&data, omit).unwrap();
+    gsl::test_rel(skew, stat::skew(&clean), 1e-15, "nan skew");
+
+    let kurtosis = nan::kurtosis(&data, omit).unwrap();
+    gsl::test_rel(kurtosis, stat::kurtosis(&clean), 1e-15, "nan kurtosis");
+
+    let median = nan::median(&data, omit).unwrap();
+    gsl::test_rel(median, 0.0857, 1e-15, "nan median");
+
+    let q = nan::quantile(&data, 0.25, omit).unwrap();
+    gsl::test_rel(q, 0.0509, 1e-15, "nan quantile");
+
+    // the positions are those in the data with the NaN values
+    let max = nan::max(&data, omit).unwrap();
+    gsl::test(max != (0.1331, 6), "nan max");
+
+    let min = nan::min(&data, omit).unwrap();
+    gsl::test(min != (0.0242, 5), "nan min");
+
+    let lag1 = nan::lag1autocorrelation(&data, omit).unwrap();
+    gsl::test_rel(lag1, stat::lag1autocorrelation(&clean), 1e-15, "nan lag1autocorrelation");
+
+    let lag1 = nan::apply(&data, omit, stat::lag1autocorrelation).unwrap();
+    gsl::test_rel(lag1, stat::lag1autocorrelation(&clean), 1e-15, "nan apply");
+
+    let mad = nan::mad(&data, omit).unwrap();
+    gsl::test_rel(mad, stat::mad(&clean), 1e-15, "nan mad");
+
+    let values = nan::values(&data, omit).unwrap();
+    gsl::test(values != clean, "nan values");
+
+    let all_nan = [nan, nan];
+    gsl::test(!nan::median(&all_nan, omit).unwrap().is_nan(), "nan median of nothing");
+    let max = nan::max(&all_nan, omit).unwrap();
+    gsl::test(!max.0.is_nan() || max.1 != 0, "nan max of nothing");
+}
+
+#[test]
+fn test_nan_omit_all() {
+    let nan = f64::NAN;
+    let all_nan = [nan, nan, nan];
+    let omit = NanPolicy::Omit;
+
+    gsl::test(!nan::mean(&all_nan, omit).unwrap().is_nan(), "nan mean of nothing");
+    gsl::test(!nan::variance(&all_nan, omit).unwrap().is_nan(), "nan variance of nothing");
+    gsl::test(!nan::sd(&all_nan, omit).unwrap().is_nan(), "nan sd of nothing");
+    gsl::test(!nan::tss(&all_nan, omit).unwrap().is_nan(), "nan tss of nothing");
+    gsl::test(!nan::absdev(&all_nan, omit).unwrap().is_nan(), "nan absdev of nothing");
+    gsl::test(!nan::skew(&all_nan, omit).unwrap().is_nan(), "nan skew of nothing");
+    gsl::test(!nan::kurtosis(&all_nan, omit).unwrap().is_nan(), "nan kurtosis of nothing");
+    gsl::test(!nan::lag1autocorrelation(&all_nan, omit).unwrap().is_nan(),
+              "nan lag1autocorrelation of nothing");
+    gsl::test(!nan::mad(&all_nan, omit).unwrap().is_nan(), "nan mad of nothing");
+
+    let x = [1.0, 2.0, 3.0];
+    gsl::test(!nan::covariance(&x, &all_nan, omit).unwrap().is_nan(),
+              "nan covariance of nothing");
+    gsl::test(!nan::correlation(&all_nan, &x, omit).unwrap().is_nan(),
+              "nan correlation of nothing");
+    gsl::test(!nan::p_variance(&x, &all_nan, omit).unwrap().is_nan(),
+              "nan p_variance of nothing");
+    gsl::test(!nan::t_test(&all_nan, &x, omit).unwrap().is_nan(), "nan t_test of nothing");
+    gsl::test(!nan::w_mean(&x, &all_nan, omit).unwrap().is_nan(), "nan w_mean of nothing");
+    gsl::test(!nan::w_variance(&x, &all_nan, omit).unwrap().is_nan(),
+              "nan w_variance of nothing");
+    gsl::test(!nan::w_kurtosis(&all_nan, &x, omit).unwrap().is_nan(),
+              "nan w_kurtosis of nothing");
+}
+
+#[test]
+fn test_nan_pairs() {
+    let nan = f64::NAN;
+    let x = [1.0, 2.0, nan, 4.0, 5.0, 6.0];
+    let y = [2.0, 4.5, 5.0, nan, 9.0, 13.0];
+    let w = [1.0, nan, 2.0, 1.0, 3.0, 1.0];
+    let (x_clean, y_clean) = ([1.0, 2.0, 5.0, 6.0], [2.0, 4.5, 9.0, 13.0]);
+    let omit = NanPolicy::Omit;
+
+    let cov = nan::covariance(&x, &y, omit).unwrap();
+    gsl::test_rel(cov, stat::covariance(&x_clean, &y_clean), 1e-15, "nan covariance");
+
+    let r = nan::correlation(&x, &y, omit).unwrap();
+    gsl::test_rel(r, stat::correlation(&x_clean, &y_clean), 1e-15, "nan correlation");
+
+    let (w_clean, d_clean) = ([1.0, 1.0, 3.0, 1.0], [1.0, 4.0, 5.0, 6.0]);
+    let wmean = nan::w_mean(&w, &x, omit).unwrap();
+    gsl::test_rel(wmean, stat::w_mean(&w_clean, &d_clean), 1e-15, "nan w_mean");
+
+    let wvariance = nan::w_variance(&w, &x, omit).unwrap();
+    gsl::test_rel(wvariance, stat::w_variance(&w_clean, &d_clean), 1e-15, "nan w_variance");
+
+    let wsd = nan::w_sd(&w, &x, omit).unwrap();
+    gsl::test_rel(wsd, stat::w_sd(&w_clean, &d_clean), 1e-15, "nan w_sd");
+
+    let wtss = nan::w_tss(&w, &x, omit).unwrap();
+    gsl::test_rel(wtss, stat::w_tss(&w_clean, &d_clean), 1e-15, "nan w_tss");
+
+    let wabsdev = nan::w_absdev(&w, &x, omit).unwrap();
+    gsl::test_rel(wabsdev, stat::w_absdev(&w_clean, &d_clean), 1e-15, "nan w_absdev");
+
+    let wskew = nan::w_skew(&w, &x, omit).unwrap();
+    gsl::test_rel(wskew, stat::w_skew(&w_clean, &d_clean), 1e-15, "nan w_skew");
+
+    let wkurtosis = nan::w_kurtosis(&w, &x, omit).unwrap();
+    gsl::test_rel(wkurtosis, stat::w_kurtosis(&w_clean, &d_clean), 1e-15, "nan w_kurtosis");
+
+    // independent samples lose their own NaN values only
+    let (x_sample, y_sample) = ([1.0, 2.0, 4.0, 5.0, 6.0], [2.0, 4.5, 5.0, 9.0, 13.0]);
+    let pv = nan::p_variance(&x, &y, omit).unwrap();
+    gsl::test_rel(pv, stat::p_variance(&x_sample, &y_sample), 1e-15, "nan p_variance");
+
+    let t = nan::t_test(&x, &y, omit).unwrap();
+    gsl::test_rel(t, stat::t_test(&x_sample, &y_sample), 1e-15, "nan t_test");
+
+    let (x_pairs, y_pairs) = nan::pairs(&x, &y, omit).unwrap();
+    gsl::test(x_pairs != x_clean || y_pairs != y_clean, "nan pairs");
+
+    // a NaN weight is not skipped like a zero weight
+    let wmean = nan::w_mean(&w, &x, NanPolicy::Propagate).unwrap();
+    gsl::test(!wmean.is_nan(), "nan w_mean propagate");
+
+    let r = nan::correlation(&x, &y, NanPolicy::Error);
+    gsl::test(r != Err(NanError { index: 2 }), "nan correlation error");
+}
+
+#[test]
+fn test_nan_policy() {
+    let nan = f64::NAN;
+    let data = [3.0, 1.0, nan, 2.0, nan];
+
+    let propagate = NanPolicy::Propagate;
+    gsl::test(!nan::mean(&data, propagate).unwrap().is_nan(), "nan mean propagate");
+    gsl::test(!nan::variance(&data, propagate).unwrap().is_nan(), "nan variance propagate");
+    gsl::test(!nan::median(&data, propagate).unwrap().is_nan(), "nan median propagate");
+    gsl::test(!nan::quantile(&data, 0.1, propagate).unwrap().is_nan(),
+              "nan quantile propagate");
+
+    let max = nan::max(&data, propagate).unwrap();
+    gsl::test(!max.0.is_nan() || max.1 != 2, "nan max propagate");
+
+    let error = NanPolicy::Error;
+    gsl::test(nan::mean(&data, error) != Err(NanError { index: 2 }), "nan mean error");
+    gsl::test(nan::median(&data, error) != Err(NanError { index: 2 }), "nan median error");
+    gsl::test(nan::min(&data, error) != Err(NanError { index: 2 }), "nan min error");
+    gsl::test(nan::mean(&[1, 2, 3], error) != Ok(2.0), "nan mean of integers");
+
+    let message = format!("{}", NanError { index: 2 });
+    gsl::test(message != "data contains NaN at position 2", "nan error message");
+}
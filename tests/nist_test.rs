@@ -424,4 +424,48 @@ fn test_nist() {
         gsl::test_rel(sd, expected_sd, 1e-7, "acc4 stat::sd");
         gsl::test_rel(lag1, expected_lag1, 1e-10, "acc4 autocorrelation");
     }
+
+    // The compensated sums find the sd of the binary values of the data up to
+    // rounding, which leaves the error of representing the decimal data in
+    // binary as the only difference with the certified values.
+    {
+        let mean = stat::mean_with(numacc3, accuracy::Accuracy::Compensated);
+        let sd = stat::sd_with(numacc3, accuracy::Accuracy::Compensated);
+        let tss = stat::tss_with(numacc3, accuracy::Accuracy::Compensated);
+
+        gsl::test_rel(mean, 1000000.2, 1e-15, "acc3 compensated mean");
+        gsl::test_rel(sd, 0.1000000000349246, 1e-15, "acc3 compensated sd");
+        gsl::test_rel(sd, 0.1, 5e-10, "acc3 compensated sd against certified");
+        gsl::test_rel(tss, 10.00000000698492, 1e-15, "acc3 compensated tss");
+    }
+
+    {
+        let mean = stat::mean_with(numacc4, accuracy::Accuracy::Compensated);
+        let sd = stat::sd_with(numacc4, accuracy::Accuracy::Compensated);
+        let tss = stat::tss_with(numacc4, accuracy::Accuracy::Compensated);
+
+        gsl::test_rel(mean, 10000000.2, 1e-15, "acc4 compensated mean");
+        gsl::test_rel(sd, 0.10000000055879354, 1e-15, "acc4 compensated sd");
+        gsl::test_rel(sd, 0.1, 1e-8, "acc4 compensated sd against certified");
+        gsl::test_rel(tss, 10.000000111758709, 1e-15, "acc4 compensated tss");
+    }
+
+    // Where the compensated sums meet the binary values, the fast sums lose
+    // the last digits to the rounding of the large mean.
+    {
+        let expected_sd = 0.10000000055879354;
+        let expected_tss = 10.000000111758709;
+
+        let fast_sd = stat::sd_with(numacc4, accuracy::Accuracy::Fast);
+        let fast_tss = stat::tss_with(numacc4, accuracy::Accuracy::Fast);
+        let sd = stat::sd_with(numacc4, accuracy::Accuracy::Compensated);
+        let tss = stat::tss_with(numacc4, accuracy::Accuracy::Compensated);
+
+        gsl::test(((fast_sd - expected_sd) / expected_sd).abs() <= 4e-16,
+                  "acc4 fast sd misses the binary value");
+        gsl::test(((fast_tss - expected_tss) / expected_tss).abs() <= 1e-15,
+                  "acc4 fast tss misses the binary value");
+        gsl::test_rel(sd, expected_sd, 4e-16, "acc4 compensated sd meets the binary value");
+        gsl::test_rel(tss, expected_tss, 1e-15, "acc4 compensated tss meets the binary value");
+    }
 }
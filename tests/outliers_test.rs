@@ -0,0 +1,170 @@
+// outliers_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::data::StridedSlice;
+use stat::outliers::*;
+
+// the data of B. Rosner's example, as used by the NIST/SEMATECH e-Handbook
+// of Statistical Methods, section 1.3.5.17.3
+const ROSNER: [f64; 54] = [-0.25, 0.68, 0.94, 1.15, 1.20, 1.26, 1.26, 1.34, 1.38, 1.43, 1.49,
+                           1.49, 1.55, 1.56, 1.58, 1.65, 1.69, 1.70, 1.76, 1.77, 1.81, 1.91,
+                           1.94, 1.96, 1.99, 2.06, 2.09, 2.10, 2.14, 2.15, 2.23, 2.24, 2.26,
+                           2.35, 2.37, 2.40, 2.47, 2.54, 2.62, 2.64, 2.90, 2.92, 2.92, 2.93,
+                           3.21, 3.26, 3.30, 3.59, 3.68, 4.30, 4.64, 5.34, 5.42, 6.01];
+
+#[test]
+fn test_mad() {
+    let slice_a = &[0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                    0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+
+    let rel = 1.0e-10;
+    gsl::test_rel(mad0(slice_a), 0.02925, rel, "mad0");
+    gsl::test_rel(mad(slice_a), 1.482602218505602 * 0.02925, rel, "mad");
+    gsl::test_rel(mad0(&[1, 1, 2, 2, 4, 6, 9]), 1.0, rel, "mad0 integer");
+}
+
+#[test]
+fn test_scores() {
+    let data = &[2.0, 3.1, 2.8, 40.0, 3.3, 2.9, -15.0, 3.0, 3.2, 2.7];
+
+    let rel = 1.0e-10;
+
+    {
+        let s = tukey_fences(data, 1.5);
+        gsl::test_rel(s.scores[0], 1.6111111111111111, rel, "tukey_fences score");
+        gsl::test_rel(s.scores[3], 81.83333333333333, rel, "tukey_fences score");
+        gsl::test_rel(s.scores[6], 39.38888888888889, rel, "tukey_fences score");
+        gsl::test(s.scores[1] != 0.0, "tukey_fences inside");
+        gsl::test(s.indices != vec![0, 3, 6], "tukey_fences indices");
+
+        // the same values as boxplot_stats
+        let b = summary::boxplot_stats(data, 3.0);
+        gsl::test(tukey_fences(data, 3.0).indices != b.outliers, "tukey_fences boxplot");
+    }
+
+    {
+        // a single outlier inflates sd, masking the other
+        let s = z_scores(data, 3.0);
+        gsl::test_rel(s.scores[3], 2.590383633929405, rel, "z_scores score");
+        gsl::test_rel(s.scores[6], -1.4570907940852904, rel, "z_scores score");
+        gsl::test(!s.indices.is_empty(), "z_scores indices");
+        gsl::test(z_scores(data, 2.0).indices != vec![3], "z_scores indices");
+
+        // every other value of interleaved data
+        let interleaved: Vec<f64> = data.iter().flat_map(|&x| vec![x, 0.0]).collect();
+        let strided = StridedSlice::new(&interleaved, 2);
+        gsl::test(z_scores(&strided, 3.0) != s, "z_scores strided");
+    }
+
+    {
+        let s = modified_z_scores(data, 3.5);
+        gsl::test_rel(s.scores[0], -2.5631, rel, "modified_z_scores score");
+        gsl::test_rel(s.scores[3], 99.9609, rel, "modified_z_scores score");
+        gsl::test_rel(s.scores[6], -48.4291, rel, "modified_z_scores score");
+        gsl::test(s.indices != vec![3, 6], "modified_z_scores indices");
+    }
+
+    {
+        // the median absolute deviation is zero
+        let s = modified_z_scores(&[5, 5, 5, 5, 9], 3.5);
+        gsl::test(s.scores[0] != 0.0, "modified_z_scores zero mad");
+        gsl::test(s.indices != vec![4], "modified_z_scores zero mad indices");
+    }
+}
+
+#[test]
+fn test_grubbs() {
+    let rel = 1.0e-8;
+
+    {
+        let t = grubbs_test(&ROSNER, 0.05);
+        gsl::test(t.index != 53, "grubbs_test index");
+        gsl::test_rel(t.statistic, 3.118906048982442, rel, "grubbs_test statistic");
+        gsl::test_rel(t.critical, 3.158793940887512, rel, "grubbs_test critical");
+        gsl::test(t.is_outlier(), "grubbs_test outlier");
+    }
+
+    // critical values of the two-sided test
+    let t = grubbs_test(&[1.0, 2.0, 4.0], 0.05);
+    gsl::test_rel(t.critical, 1.1543048513440384, rel, "grubbs_test critical n = 3");
+
+    let data10 = &[199.31, 199.53, 200.19, 200.82, 201.92, 201.95, 202.18, 245.57, 200.5, 201.1];
+    let t = grubbs_test(data10, 0.05);
+    gsl::test_rel(t.critical, 2.2899540844796004, rel, "grubbs_test critical n = 10");
+    gsl::test(t.index != 7 || !t.is_outlier(), "grubbs_test outlier");
+
+    {
+        // the first step masks the outliers that generalized_esd finds
+        let s = grubbs_iterative(&ROSNER, 0.05);
+        gsl::test(s.steps.len() != 1, "grubbs_iterative steps");
+        gsl::test(!s.indices.is_empty(), "grubbs_iterative indices");
+
+        let s = grubbs_iterative(data10, 0.05);
+        gsl::test(s.indices != vec![7], "grubbs_iterative outlier");
+        gsl::test(s.steps.len() != 2 || s.steps[1].is_outlier(), "grubbs_iterative last step");
+    }
+}
+
+#[test]
+fn test_generalized_esd() {
+    let rel = 1.0e-8;
+
+    let statistics = [3.118906048982442, 2.942973113643507, 3.179423936717836,
+                      2.8101811444275904, 2.8155795634442766, 2.848171627930342,
+                      2.2793270549903433, 2.3103660590543003, 2.101580651024144,
+                      2.0671780780253646];
+    let critical = [3.158793941, 3.151430023, 3.143889685, 3.136164956, 3.128247334,
+                    3.120127738, 3.111796454, 3.103243078, 3.094456447, 3.085424571];
+    let indices = [53, 52, 51, 50, 0, 49, 48, 47, 1, 46];
+
+    let s = generalized_esd(&ROSNER, 10, 0.05);
+    gsl::test(s.steps.len() != 10, "generalized_esd steps");
+    for (i, step) in s.steps.iter().enumerate() {
+        gsl::test(step.index != indices[i], "generalized_esd index");
+        gsl::test_rel(step.statistic, statistics[i], rel, "generalized_esd statistic");
+        gsl::test_rel(step.critical, critical[i], 1.0e-9, "generalized_esd critical");
+    }
+    gsl::test(s.indices != vec![53, 52, 51], "generalized_esd outliers");
+
+    let s = generalized_esd(&ROSNER, 2, 0.05);
+    gsl::test(!s.indices.is_empty(), "generalized_esd no outliers");
+}
+
+#[test]
+fn test_dixon_q() {
+    let data = &[0.189, 0.167, 0.187, 0.183, 0.186, 0.182, 0.181, 0.184, 0.181, 0.177];
+
+    let rel = 1.0e-10;
+
+    let t = dixon_q_test(data, 0.05);
+    gsl::test(t.index != 1, "dixon_q_test index");
+    gsl::test_rel(t.statistic, 0.45454545454545454, rel, "dixon_q_test statistic");
+    gsl::test_rel(t.critical, 0.466, rel, "dixon_q_test critical");
+    gsl::test(t.is_outlier(), "dixon_q_test 0.05");
+    gsl::test(!dixon_q_test(data, 0.10).is_outlier(), "dixon_q_test 0.10");
+
+    let t = dixon_q_test(&[3u32, 4, 5, 12], 0.01);
+    gsl::test(t.index != 3, "dixon_q_test high index");
+    gsl::test_rel(t.statistic, 7.0 / 9.0, rel, "dixon_q_test high statistic");
+    gsl::test(t.is_outlier(), "dixon_q_test high 0.01");
+}
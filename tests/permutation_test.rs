@@ -0,0 +1,162 @@
+// permutation_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::permutation::*;
+use stat::rng::{Pcg64, Xoshiro256StarStar, MT19937};
+
+fn mean_difference(a: &[f64], b: &[f64]) -> f64 {
+    mean(a) - mean(b)
+}
+
+fn median_difference(a: &[f64], b: &[f64]) -> f64 {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    b.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    median_from_sorted_data(&a) - median_from_sorted_data(&b)
+}
+
+#[test]
+fn test_permutation_exact() {
+    let data1 = &[19, 22, 16, 29, 24];
+    let data2 = &[20, 11, 17, 12];
+
+    let rel = 1.0e-10;
+    let mut rng = Xoshiro256StarStar::new(7);
+
+    {
+        let t = permutation_test(data1, data2, mean_difference, 1000, Alternative::TwoSided, &mut rng);
+        gsl::test(!t.exact, "exact");
+        gsl::test(t.n_permutations != 126, "exact n_permutations");
+        gsl::test_rel(t.statistic, 7.0, rel, "exact statistic");
+        gsl::test_rel(t.p_value, 0.07936507936507936, rel, "exact two-sided p_value");
+    }
+
+    {
+        let t = permutation_test(data1, data2, mean_difference, 1000, Alternative::Greater, &mut rng);
+        gsl::test_rel(t.p_value, 0.03968253968253968, rel, "exact greater p_value");
+
+        let t = permutation_test(data1, data2, mean_difference, 1000, Alternative::Less, &mut rng);
+        gsl::test_rel(t.p_value, 0.9682539682539683, rel, "exact less p_value");
+    }
+
+    {
+        let t = permutation_test(data1,
+                                 data2,
+                                 median_difference,
+                                 1000,
+                                 Alternative::Greater,
+                                 &mut rng);
+        gsl::test_rel(t.statistic, 7.5, rel, "exact median statistic");
+        gsl::test_rel(t.p_value, 0.03968253968253968, rel, "exact median p_value");
+    }
+}
+
+#[test]
+fn test_permutation_monte_carlo() {
+    let data1 = &[19, 22, 16, 29, 24];
+    let data2 = &[20, 11, 17, 12];
+
+    {
+        let mut rng = Xoshiro256StarStar::new(7);
+        let t = permutation_test(data1, data2, mean_difference, 100, Alternative::TwoSided, &mut rng);
+        gsl::test(t.exact, "monte carlo");
+        gsl::test(t.n_permutations != 100, "monte carlo n_permutations");
+        gsl::test(t.p_value <= 0.0 || t.p_value > 1.0, "monte carlo p_value range");
+
+        let mut rng = Xoshiro256StarStar::new(7);
+        let again = permutation_test(data1, data2, mean_difference, 100, Alternative::TwoSided, &mut rng);
+        gsl::test(again != t, "monte carlo reproducible with the same seed");
+    }
+
+    {
+        let mut rng = Xoshiro256StarStar::new(11);
+        let t = permutation_test(data1,
+                                 data2,
+                                 mean_difference,
+                                 125,
+                                 Alternative::Greater,
+                                 &mut rng);
+        gsl::test_rel(t.p_value, 0.03968253968253968, 0.6, "monte carlo greater p_value");
+    }
+
+    {
+        let a: Vec<f64> = (0..40).map(|i| (i as f64 * 0.37).sin()).collect();
+        let b: Vec<f64> = (0..40).map(|i| (i as f64 * 0.53).cos() + 0.1).collect();
+
+        let mut rng = Xoshiro256StarStar::new(3);
+        let single = permutation_test(&a, &b, mean_difference, 20000, Alternative::TwoSided, &mut rng);
+
+        let mut rng = Xoshiro256StarStar::new(3);
+        let parallel = permutation_test_parallel(&a,
+                                                 &b,
+                                                 mean_difference,
+                                                 20000,
+                                                 Alternative::TwoSided,
+                                                 &mut rng,
+                                                 4);
+
+        let mut rng = Xoshiro256StarStar::new(3);
+        let again = permutation_test_parallel(&a,
+                                              &b,
+                                              mean_difference,
+                                              20000,
+                                              Alternative::TwoSided,
+                                              &mut rng,
+                                              4);
+
+        gsl::test(parallel != again, "parallel reproducible with the same seed");
+        gsl::test(parallel.n_permutations != 20000, "parallel n_permutations");
+        gsl::test_rel(parallel.statistic, single.statistic, 1.0e-10, "parallel statistic");
+        gsl::test((parallel.p_value - single.p_value).abs() > 0.02, "parallel p_value");
+    }
+
+    {
+        let a: Vec<f64> = (0..40).map(|i| (i as f64 * 0.37).sin()).collect();
+        let b: Vec<f64> = (0..40).map(|i| (i as f64 * 0.53).cos() + 0.1).collect();
+
+        let mut rng = Xoshiro256StarStar::new(3);
+        let single = permutation_test(&a, &b, mean_difference, 20000, Alternative::TwoSided, &mut rng);
+
+        let mut rng = Pcg64::new(3);
+        let pcg = permutation_test_parallel(&a,
+                                            &b,
+                                            mean_difference,
+                                            20000,
+                                            Alternative::TwoSided,
+                                            &mut rng,
+                                            4);
+
+        let mut rng = MT19937::new(3);
+        let mt = permutation_test_parallel(&a,
+                                           &b,
+                                           mean_difference,
+                                           20000,
+                                           Alternative::TwoSided,
+                                           &mut rng,
+                                           4);
+
+        gsl::test((pcg.p_value - single.p_value).abs() > 0.02, "parallel pcg64 p_value");
+        gsl::test((mt.p_value - single.p_value).abs() > 0.02, "parallel mt19937 p_value");
+    }
+}
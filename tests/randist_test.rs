@@ -0,0 +1,263 @@
+// randist_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::randist::*;
+use stat::rng::*;
+
+const N: usize = 200000;
+
+// test_moments compares the sample mean and variance with those of the
+// distribution, the tolerances being several standard errors wide
+fn test_moments(data: &[f64], m: f64, v: f64, rel: f64, desc: &str) {
+    gsl::test_rel(mean(data), m, rel, &format!("{} mean", desc));
+    gsl::test_rel(variance(data), v, rel, &format!("{} variance", desc));
+}
+
+#[test]
+fn test_continuous() {
+    let mut rng = Xoshiro256StarStar::new(1);
+
+    {
+        let data: Vec<f64> = (0..N).map(|_| gaussian(&mut rng, 2.0)).collect();
+        gsl::test(mean(&data).abs() > 0.02, "gaussian mean");
+        gsl::test_rel(variance(&data), 4.0, 0.02, "gaussian variance");
+        gsl::test(skew(&data).abs() > 0.02, "gaussian skew");
+        gsl::test(kurtosis(&data).abs() > 0.05, "gaussian kurtosis");
+
+        // the tail beyond the base layer of the ziggurat
+        let tail = data.iter().filter(|&&x| x.abs() > 2.0 * 3.442619855899).count();
+        gsl::test_rel(tail as f64, 115.2, 0.3, "gaussian tail");
+    }
+
+    {
+        let data: Vec<f64> = (0..N).map(|_| exponential(&mut rng, 3.0)).collect();
+        test_moments(&data, 3.0, 9.0, 0.02, "exponential");
+    }
+
+    for &(a, b) in &[(0.5, 2.0), (1.0, 1.0), (4.5, 0.5)] {
+        let data: Vec<f64> = (0..N).map(|_| gamma(&mut rng, a, b)).collect();
+        test_moments(&data, a * b, a * b * b, 0.03, &format!("gamma({}, {})", a, b));
+    }
+
+    {
+        let (a, b) = (2.0, 5.0);
+        let data: Vec<f64> = (0..N).map(|_| beta(&mut rng, a, b)).collect();
+        let v = a * b / ((a + b) * (a + b) * (a + b + 1.0));
+        test_moments(&data, a / (a + b), v, 0.02, "beta");
+    }
+
+    {
+        let data: Vec<f64> = (0..N).map(|_| chisq(&mut rng, 7.0)).collect();
+        test_moments(&data, 7.0, 14.0, 0.02, "chisq");
+    }
+
+    {
+        let data: Vec<f64> = (0..N).map(|_| tdist(&mut rng, 10.0)).collect();
+        gsl::test(mean(&data).abs() > 0.02, "tdist mean");
+        gsl::test_rel(variance(&data), 10.0 / 8.0, 0.03, "tdist variance");
+    }
+}
+
+#[test]
+fn test_discrete() {
+    let mut rng = MT19937::new(0);
+
+    for &mu in &[0.5, 4.0, 37.5, 1000.0] {
+        let data: Vec<u64> = (0..N).map(|_| poisson(&mut rng, mu)).collect();
+        gsl::test_rel(mean(&data), mu, 0.02, &format!("poisson({}) mean", mu));
+        gsl::test_rel(variance(&data), mu, 0.03, &format!("poisson({}) variance", mu));
+    }
+
+    for &(p, n) in &[(0.3, 5), (0.7, 60), (0.01, 10000)] {
+        let data: Vec<u64> = (0..N).map(|_| binomial(&mut rng, p, n)).collect();
+        gsl::test(data.iter().any(|&k| k > n), "binomial range");
+        let m = n as f64 * p;
+        gsl::test_rel(mean(&data), m, 0.02, &format!("binomial({}, {}) mean", p, n));
+        gsl::test_rel(variance(&data), m * (1.0 - p), 0.03,
+                      &format!("binomial({}, {}) variance", p, n));
+    }
+
+    gsl::test(binomial(&mut rng, 0.0, 100) != 0, "binomial p = 0");
+    gsl::test(binomial(&mut rng, 1.0, 100) != 100, "binomial p = 1");
+}
+
+#[test]
+fn test_multinomial() {
+    let mut rng = Pcg64::new(7);
+    let p = [1.0, 0.0, 3.0, 4.0];
+
+    let mut total = [0u64; 4];
+    for _ in 0..10000 {
+        let n = multinomial(&mut rng, 50, &p);
+        gsl::test(n.iter().sum::<u64>() != 50, "multinomial sum");
+        for (t, k) in total.iter_mut().zip(n) {
+            *t += k;
+        }
+    }
+
+    gsl::test(total[1] != 0, "multinomial zero probability");
+    gsl::test_rel(total[0] as f64, 62500.0, 0.02, "multinomial n[0]");
+    gsl::test_rel(total[2] as f64, 187500.0, 0.02, "multinomial n[2]");
+    gsl::test_rel(total[3] as f64, 250000.0, 0.02, "multinomial n[3]");
+}
+
+#[test]
+fn test_alias_table() {
+    let mut rng = Pcg64::new(3);
+    let w = [2.0, 0.0, 5.0, 1.0, 12.0];
+    let d = Discrete::new(&w);
+
+    gsl::test_rel(d.pdf(2), 0.25, 1.0e-15, "discrete pdf");
+    gsl::test(d.pdf(1) != 0.0 || d.pdf(5) != 0.0, "discrete pdf zero");
+
+    let mut counts = [0usize; 5];
+    for _ in 0..N {
+        counts[d.sample(&mut rng)] += 1;
+    }
+
+    gsl::test(counts[1] != 0, "discrete zero weight");
+    for (k, &c) in counts.iter().enumerate() {
+        gsl::test(((c as f64 / N as f64) - d.pdf(k)).abs() > 0.005,
+                  &format!("discrete frequency {}", k));
+    }
+}
+
+#[test]
+fn test_shuffle() {
+    let mut rng = Xoshiro256StarStar::new(11);
+
+    // all 6 orders of 3 values are equally likely
+    let mut counts = [0usize; 6];
+    for _ in 0..60000 {
+        let mut x = [0, 1, 2];
+        shuffle(&mut rng, &mut x);
+        let k = match x {
+            [0, 1, 2] => 0,
+            [0, 2, 1] => 1,
+            [1, 0, 2] => 2,
+            [1, 2, 0] => 3,
+            [2, 0, 1] => 4,
+            _ => 5,
+        };
+        counts[k] += 1;
+    }
+    for &c in &counts {
+        gsl::test_rel(c as f64, 10000.0, 0.05, "shuffle frequency");
+    }
+
+    let mut x: Vec<usize> = (0..100).collect();
+    shuffle(&mut rng, &mut x);
+    gsl::test(x.iter().zip(0..).all(|(&a, b)| a == b), "shuffle order");
+    x.sort();
+    gsl::test(x.iter().zip(0..).any(|(&a, b)| a != b), "shuffle permutation");
+
+    let mut empty: [f64; 0] = [];
+    shuffle(&mut rng, &mut empty);
+}
+
+#[test]
+fn test_choose_and_sample() {
+    let mut rng = Pcg64::new(5);
+    let src: Vec<usize> = (0..10).collect();
+
+    let mut counts = [0usize; 10];
+    for _ in 0..50000 {
+        let c = choose(&mut rng, &src, 3);
+        gsl::test(c.len() != 3, "choose length");
+        gsl::test(c.windows(2).any(|w| w[0] >= w[1]), "choose order");
+        for &i in &c {
+            counts[i] += 1;
+        }
+    }
+    for &c in &counts {
+        gsl::test_rel(c as f64, 15000.0, 0.03, "choose frequency");
+    }
+
+    gsl::test(choose(&mut rng, &src, 10) != src, "choose all");
+    gsl::test(!choose(&mut rng, &src, 0).is_empty(), "choose none");
+
+    let mut counts = [0usize; 10];
+    let s = sample(&mut rng, &src, 100000);
+    for &i in &s {
+        counts[i] += 1;
+    }
+    for &c in &counts {
+        gsl::test_rel(c as f64, 10000.0, 0.03, "sample frequency");
+    }
+
+    // with replacement k may exceed the number of values
+    gsl::test(sample(&mut rng, &[1.5], 3) != vec![1.5, 1.5, 1.5], "sample replacement");
+}
+
+#[test]
+fn test_weighted_sample() {
+    let mut rng = Xoshiro256StarStar::new(8);
+    let src = ['a', 'b', 'c', 'd'];
+    let w = [1.0, 0.0, 2.0, 5.0];
+
+    let mut first = [0usize; 4];
+    for _ in 0..80000 {
+        let s = weighted_sample(&mut rng, &src, &w, 2);
+        gsl::test(s.len() != 2 || s[0] == s[1], "weighted_sample distinct");
+        gsl::test(s.contains(&'b'), "weighted_sample zero weight");
+        first[src.iter().position(|&c| c == s[0]).unwrap()] += 1;
+    }
+
+    // the first value is drawn with probability proportional to its weight
+    gsl::test_rel(first[0] as f64, 10000.0, 0.05, "weighted_sample first a");
+    gsl::test_rel(first[2] as f64, 20000.0, 0.05, "weighted_sample first c");
+    gsl::test_rel(first[3] as f64, 50000.0, 0.05, "weighted_sample first d");
+
+    let mut all = weighted_sample(&mut rng, &src, &w, 3);
+    all.sort();
+    gsl::test(all != vec!['a', 'c', 'd'], "weighted_sample all");
+}
+
+#[test]
+fn test_reservoir() {
+    let mut rng = MT19937::new(42);
+
+    let mut counts = [0usize; 20];
+    for _ in 0..20000 {
+        let mut r = Reservoir::new(5);
+        for i in 0..20 {
+            r.add(&mut rng, i);
+        }
+        gsl::test(r.seen() != 20, "reservoir seen");
+        gsl::test(r.sample().len() != 5, "reservoir length");
+        for &i in r.sample() {
+            counts[i] += 1;
+        }
+    }
+
+    // every value is kept with probability k/n
+    for &c in &counts {
+        gsl::test_rel(c as f64, 5000.0, 0.05, "reservoir frequency");
+    }
+
+    let mut r = Reservoir::new(5);
+    for i in 0..3 {
+        r.add(&mut rng, i);
+    }
+    gsl::test(r.into_sample() != vec![0, 1, 2], "reservoir short stream");
+}
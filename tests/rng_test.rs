@@ -0,0 +1,116 @@
+// rng_test.rs
+//
+// Copyright (C) 1996, 1997, 1998, 1999, 2000, 2007 James Theiler, Brian Gough
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::rng::*;
+
+fn test_sequence<R: Rng>(rng: &mut R, expected: &[u64], description: &str) {
+    for &e in expected {
+        let r = rng.next_u64();
+        let str = format!("{} ({} observed vs {} expected)", description, r, e);
+        gsl::test(r != e, &str);
+    }
+}
+
+fn test_uniform<R: Rng>(rng: &mut R, description: &str) {
+    let mut sum = 0.0;
+    let mut counts = [0; 7];
+    for _ in 0..70000 {
+        let u = rng.uniform();
+        gsl::test(!(0.0..1.0).contains(&u), description);
+        sum += u;
+
+        let k = rng.uniform_int(7);
+        gsl::test(k >= 7, description);
+        counts[k as usize] += 1;
+
+        let p = rng.uniform_pos();
+        gsl::test(!(p > 0.0 && p < 1.0), description);
+    }
+
+    gsl::test_rel(sum / 70000.0, 0.5, 1.0e-2, description);
+    for &c in &counts {
+        gsl::test_rel(c as f64, 10000.0, 5.0e-2, description);
+    }
+}
+
+#[test]
+fn test_xoshiro256starstar_uniform() {
+    let mut rng = Xoshiro256StarStar::new(42);
+    test_uniform(&mut rng, "xoshiro256** uniform");
+}
+
+#[test]
+fn test_pcg64() {
+    // the output of the pcg64 demo of the reference implementation
+    let mut rng = Pcg64::with_stream(42, 54);
+    test_sequence(&mut rng,
+                  &[0x86b1da1d72062b68, 0x1304aa46c9853d39, 0xa3670e9e0dd50358],
+                  "pcg64 next_u64");
+
+    let mut rng = Pcg64::new(42);
+    test_sequence(&mut rng,
+                  &[2915081201720324186, 13533757442135995717, 13172715927431628928],
+                  "pcg64 default stream");
+
+    test_uniform(&mut rng, "pcg64 uniform");
+}
+
+#[test]
+fn test_mt19937() {
+    // the 1000th value for the seed 4357, as checked by GSL's rng test
+    {
+        let mut rng = MT19937::new(4357);
+        let mut k = 0;
+        for _ in 0..1000 {
+            k = rng.get();
+        }
+        let str = format!("mt19937 get ({} observed vs {} expected)", k, 1186927261);
+        gsl::test(k != 1186927261, &str);
+    }
+
+    {
+        let mut rng = MT19937::new(1);
+        for &e in &[1791095845, 4282876139, 3093770124] {
+            let k = rng.get();
+            let str = format!("mt19937 get seed 1 ({} observed vs {} expected)", k, e);
+            gsl::test(k != e, &str);
+        }
+    }
+
+    {
+        // zero selects the default seed 4357
+        let mut rng = MT19937::new(0);
+        gsl::test_rel(rng.uniform(), 0.999741748906672, 1.0e-15, "mt19937 uniform");
+    }
+
+    {
+        let mut rng = MT19937::new(0);
+        let k: Vec<u64> = (0..10).map(|_| rng.uniform_int(10)).collect();
+        let expected = [9, 1, 2, 9, 2, 4, 9, 7, 5, 7];
+        let str = format!("mt19937 uniform_int ({:?} observed vs {:?} expected)", k, expected);
+        gsl::test(k != expected, &str);
+    }
+
+    let mut rng = MT19937::new(5);
+    test_uniform(&mut rng, "mt19937 uniform");
+}
@@ -0,0 +1,64 @@
+// statistics_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::data::StridedSlice;
+use stat::statistics::Statistics;
+
+#[test]
+fn test_statistics() {
+    let slice_a = [0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                   0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+    let w = [0.0, 1.0, 2.0, 3.0, 4.0, 0.5, 1.5, 2.5, 3.5, 1.0, 1.0, 2.0, 2.0, 1.0];
+
+    let v = slice_a.to_vec();
+    let s = &slice_a[..];
+
+    // the methods are the functions of the same name
+    gsl::test(slice_a.mean() != stat::mean(&slice_a), "method mean");
+    gsl::test(v.variance() != stat::variance(&v), "method variance");
+    gsl::test(s.sd() != stat::sd(s), "method sd");
+    gsl::test(s.skew() != stat::skew(s), "method skew");
+    gsl::test(v.kurtosis() != stat::kurtosis(&v), "method kurtosis");
+    gsl::test(s.minimum() != (0.0242, 3), "method minimum");
+    gsl::test(v.maximum() != (0.1331, 4), "method maximum");
+    gsl::test(v.w_mean(&w) != stat::w_mean(&w, &v), "method w_mean");
+    gsl::test(s.w_variance(&w) != stat::w_variance(&w, s), "method w_variance");
+    gsl::test(s.w_sd(&w) != stat::w_sd(&w, s), "method w_sd");
+    gsl::test(s.w_skew(&w) != stat::w_skew(&w, s), "method w_skew");
+    gsl::test(s.w_kurtosis(&w) != stat::w_kurtosis(&w, s), "method w_kurtosis");
+
+    // median and quantile don't need sorted data
+    let rel = 1.0e-10;
+    gsl::test_rel(v.median(), 0.07505, rel, "method median");
+    gsl::test_rel(s.quantile(0.25), 0.04355, rel, "method quantile");
+    gsl::test(v != slice_a.to_vec(), "method data unchanged");
+
+    let interleaved = [1.0, 10.0, 3.0, 30.0, 2.0, 20.0];
+    let even = StridedSlice::new(&interleaved, 2);
+    gsl::test(even.mean() != 2.0 || even.median() != 2.0, "method strided");
+
+    let ints = vec![3, 9, 1, 4];
+    gsl::test(ints.mean() != 4.25, "method integer mean");
+    gsl::test(ints.maximum() != (9.0, 1), "method integer maximum");
+    gsl::test(ints[..].minimum() != (1.0, 2), "method integer minimum");
+    gsl::test([3, 9, 1, 4].maximum() != (9.0, 1), "method integer array maximum");
+}
@@ -0,0 +1,140 @@
+// summary_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use stat::*;
+use stat::summary::*;
+
+#[test]
+fn test_describe() {
+    let slice_a = &[0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                    0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+
+    let rel = 1.0e-10;
+    let s = describe(slice_a);
+
+    gsl::test(s.n != 14, "describe n");
+    gsl::test_rel(s.mean, mean(slice_a), rel, "describe mean");
+    gsl::test_rel(s.variance, variance(slice_a), rel, "describe variance");
+    gsl::test_rel(s.sd, sd(slice_a), rel, "describe sd");
+    gsl::test_rel(s.sem, 0.009357737587005355, rel, "describe sem");
+    gsl::test_rel(s.skew, skew(slice_a), rel, "describe skew");
+    gsl::test_rel(s.kurtosis, kurtosis(slice_a), rel, "describe kurtosis");
+    gsl::test_rel(s.min, 0.0242, rel, "describe min");
+    gsl::test_rel(s.max, 0.1331, rel, "describe max");
+    gsl::test_rel(s.range, 0.1089, rel, "describe range");
+    gsl::test_rel(s.sum, 1.0192, rel, "describe sum");
+
+    let igroupa = &[17i32, 18, 16, 18, 12, 20, 18, 20, 20, 22, 20, 10, 8, 12, 16, 16, 18, 20,
+                    18, 21];
+    let s = describe(igroupa);
+    gsl::test_rel(s.mean, 17.0, rel, "describe integer mean");
+    gsl::test_rel(s.variance, variance(igroupa), rel, "describe integer variance");
+    gsl::test_rel(s.skew, skew(igroupa), rel, "describe integer skew");
+    gsl::test_rel(s.kurtosis, kurtosis(igroupa), rel, "describe integer kurtosis");
+    gsl::test_rel(s.sum, 340.0, rel, "describe integer sum");
+    gsl::test_rel(s.range, 14.0, rel, "describe integer range");
+
+    let s = describe(&[3.0]);
+    gsl::test(s.mean != 3.0 || s.range != 0.0, "describe single value");
+    gsl::test(!s.variance.is_nan(), "describe single value variance");
+
+    let s = describe(&[1.0, f64::NAN, 2.0]);
+    gsl::test(!s.min.is_nan() || !s.max.is_nan(), "describe nan");
+}
+
+#[test]
+fn test_describe_display() {
+    let s = describe(&[1.0, 2.0, 3.0, 4.0]);
+    let table = format!("{}", s);
+    let lines: Vec<&str> = table.lines().collect();
+
+    gsl::test(lines.len() != 11, "describe display rows");
+    gsl::test(lines[0] != "n                        4", "describe display n");
+    gsl::test(lines[1] != "mean              2.500000", "describe display mean");
+    gsl::test(lines[6] != "kurtosis         -2.077500", "describe display kurtosis");
+    gsl::test(lines[10] != "sum              10.000000", "describe display sum");
+
+    let s = describe(&[1.0e-6, 2.0e-6]);
+    gsl::test(!format!("{}", s).contains("mean           1.500000e-6"), "describe display exponent");
+}
+
+#[test]
+fn test_five_number_summary() {
+    let slice_a = &[0.0421, 0.0941, 0.1064, 0.0242, 0.1331, 0.0773, 0.0243, 0.0815, 0.1186,
+                    0.0356, 0.0728, 0.0999, 0.0614, 0.0479];
+
+    let rel = 1.0e-10;
+    let s = five_number_summary(slice_a);
+
+    gsl::test_rel(s.min, 0.0242, rel, "five_number_summary min");
+    gsl::test_rel(s.q1, 0.04355, rel, "five_number_summary q1");
+    gsl::test_rel(s.median, 0.07505, rel, "five_number_summary median");
+    gsl::test_rel(s.q3, 0.09845, rel, "five_number_summary q3");
+    gsl::test_rel(s.max, 0.1331, rel, "five_number_summary max");
+    gsl::test_rel(s.iqr(), 0.0549, rel, "five_number_summary iqr");
+
+    let s = five_number_summary(&[7u8]);
+    gsl::test(s.min != 7.0 || s.q1 != 7.0 || s.median != 7.0 || s.q3 != 7.0 || s.max != 7.0,
+              "five_number_summary single value");
+}
+
+#[test]
+fn test_boxplot_stats() {
+    let data = &[2.0, 3.1, 2.8, 40.0, 3.3, 2.9, -15.0, 3.0, 3.2, 2.7];
+
+    let rel = 1.0e-10;
+
+    {
+        let b = boxplot_stats(data, 1.5);
+        gsl::test_rel(b.summary.q1, 2.725, rel, "boxplot_stats q1");
+        gsl::test_rel(b.summary.median, 2.95, rel, "boxplot_stats median");
+        gsl::test_rel(b.summary.q3, 3.175, rel, "boxplot_stats q3");
+        gsl::test_rel(b.iqr, 0.45, rel, "boxplot_stats iqr");
+        gsl::test_rel(b.lower_fence, 2.05, rel, "boxplot_stats lower_fence");
+        gsl::test_rel(b.upper_fence, 3.85, rel, "boxplot_stats upper_fence");
+        gsl::test(b.lower_whisker != 2.7, "boxplot_stats lower_whisker");
+        gsl::test(b.upper_whisker != 3.3, "boxplot_stats upper_whisker");
+        gsl::test(b.outliers != vec![0, 3, 6], "boxplot_stats outliers");
+    }
+
+    {
+        let b = boxplot_stats(data, 3.0);
+        gsl::test(b.lower_whisker != 2.0, "boxplot_stats wide lower_whisker");
+        gsl::test(b.upper_whisker != 3.3, "boxplot_stats wide upper_whisker");
+        gsl::test(b.outliers != vec![3, 6], "boxplot_stats wide outliers");
+    }
+
+    {
+        let b = boxplot_stats(&[1, 2, 3, 4, 5], 1.5);
+        gsl::test(b.lower_whisker != 1.0 || b.upper_whisker != 5.0, "boxplot_stats whiskers");
+        gsl::test(!b.outliers.is_empty(), "boxplot_stats no outliers");
+    }
+
+    {
+        let inf = f64::INFINITY;
+        let b = boxplot_stats(&[-inf, -inf, -inf, 1.0], 1.5);
+        gsl::test(!b.upper_fence.is_nan(), "boxplot_stats undefined fence");
+        gsl::test(!b.lower_whisker.is_nan() || !b.upper_whisker.is_nan(),
+                  "boxplot_stats undefined whiskers");
+        gsl::test(!b.outliers.is_empty(), "boxplot_stats undefined outliers");
+    }
+}
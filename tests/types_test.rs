@@ -0,0 +1,93 @@
+// types_test.rs
+//
+// Copyright (C) 2016 G.vd.Schoot
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation; either version 2 of the License, or (at
+// your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program; if not, write to the Free Software
+// Foundation, Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301, USA.
+//
+
+mod gsl;
+
+extern crate stat;
+use std::collections::HashMap;
+use std::num::{NonZeroU32, Wrapping};
+use std::time::Duration;
+
+use stat::*;
+use stat::iter::StatsIterExt;
+
+#[derive(Clone)]
+struct Meters(f32);
+
+impl From<Meters> for f64 {
+    fn from(m: Meters) -> f64 {
+        m.0 as f64
+    }
+}
+
+impl_f64!(Meters);
+
+#[test]
+fn test_types() {
+    let rel = 1.0e-15;
+
+    // 128 bit integers beyond the range of i64
+    let big = [170141183460469231731687303715884105727i128, -1i128 << 100];
+    gsl::test_rel(max(&big).0, 2f64.powi(127), rel, "i128 max");
+    gsl::test_rel(min(&big).0, -2f64.powi(100), rel, "i128 min");
+    gsl::test_rel(mean(&[u128::MAX, 0]), 2f64.powi(127), rel, "u128 mean");
+
+    let flags = [true, false, true, true];
+    gsl::test_rel(mean(&flags), 0.75, rel, "bool mean");
+
+    let latencies = [Duration::from_millis(120), Duration::from_millis(80), Duration::from_secs(1)];
+    gsl::test_rel(mean(&latencies), 0.4, rel, "duration mean");
+    gsl::test_rel(max(&latencies).0, 1.0, rel, "duration max");
+
+    let counters = [Wrapping(3u8), Wrapping(250u8) + Wrapping(10)];
+    gsl::test_rel(mean(&counters), 3.5, rel, "wrapping mean");
+
+    let ids = [NonZeroU32::new(2).unwrap(), NonZeroU32::new(6).unwrap()];
+    gsl::test_rel(variance(&ids), 8.0, rel, "nonzero variance");
+
+    let readings = [Some(2), None, Some(4)];
+    gsl::test(!mean(&readings).is_nan(), "option mean");
+    let m = nan::mean(&readings, nan::NanPolicy::Omit).unwrap();
+    gsl::test_rel(m, 3.0, rel, "option nan mean");
+
+    let boxed = vec![Box::new(1.5), Box::new(2.5)];
+    gsl::test_rel(mean(&boxed), 2.0, rel, "box mean");
+
+    let distances = vec![Meters(1.0), Meters(2.0), Meters(6.0)];
+    gsl::test_rel(mean(&distances), 3.0, rel, "newtype mean");
+    gsl::test_rel(sd(&distances), 7f64.sqrt(), rel, "newtype sd");
+}
+
+#[test]
+fn test_references() {
+    let rel = 1.0e-15;
+
+    // slices of references, as collected from other containers
+    let x = [4.0, 8.0, 9.0];
+    let refs: Vec<&f64> = x.iter().collect();
+    gsl::test_rel(mean(&refs), 7.0, rel, "reference mean");
+
+    // iterators over references need no copying
+    let mut m = HashMap::new();
+    m.insert("a", 2u16);
+    m.insert("b", 4u16);
+    m.insert("c", 9u16);
+    gsl::test_rel(m.values().mean(), 5.0, rel, "reference iter mean");
+    gsl::test_rel(x.iter().variance(), 7.0, rel, "reference iter variance");
+}